@@ -1,5 +1,5 @@
-use crate::TokenStream2;
-use syn::{spanned::Spanned, Attribute};
+use crate::{constants, profile, TokenStream2};
+use syn::{punctuated::Punctuated, spanned::Spanned, Attribute, LitStr, Meta, Token};
 
 fn find_attribute_and_duplicates<'a>(
     attrs: &'a [Attribute],
@@ -33,3 +33,78 @@ pub fn find_attribute_unique<'l>(
 
     Some(attr)
 }
+
+/// Finds the attributes named `ident`, allowing only one of them per profile.
+pub fn find_attribute_unique_per_profile<'l>(
+    attrs: &'l [Attribute],
+    ident: &str,
+    error_tokens: &mut Vec<TokenStream2>,
+) -> Vec<(Option<LitStr>, &'l Attribute)> {
+    let mut found: Vec<(Option<LitStr>, &Attribute)> = Vec::new();
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().get_ident().is_some_and(|i| i == ident))
+    {
+        let Some(profile) = handle_error!(profile::get_profile(attr), error_tokens) else {
+            continue;
+        };
+
+        if found
+            .iter()
+            .any(|(p, _)| profile::is_same_profile(p.as_ref(), profile.as_ref()))
+        {
+            match &profile {
+                Some(name) => error!(
+                    error_tokens,
+                    attr.meta.span(),
+                    "this attribute is already declared for the profile `{}`.",
+                    name.value()
+                ),
+                None => error!(
+                    error_tokens,
+                    attr.meta.span(),
+                    "this attribute is already declared."
+                ),
+            }
+
+            continue;
+        }
+
+        found.push((profile, attr));
+    }
+
+    found
+}
+
+/// Gets the `cfg` attributes of a field, to put them on the code generated for it.
+///
+/// Only the `cfg` attributes of a `cfg_attr` are kept, the others being unknown outside of the fields.
+pub fn cfg_attributes(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter_map(|attr| {
+            if attr.path().is_ident(constants::CFG_IDENT) {
+                return Some(attr.clone());
+            }
+            if !attr.path().is_ident("cfg_attr") {
+                return None;
+            }
+
+            let (predicate, attributes) = attr
+                .parse_args_with(|input: syn::parse::ParseStream| {
+                    let predicate: Meta = input.parse()?;
+                    input.parse::<Token![,]>()?;
+
+                    Ok((predicate, Punctuated::<Meta, Token![,]>::parse_terminated(input)?))
+                })
+                .ok()?;
+            let cfgs: Vec<_> = attributes
+                .iter()
+                .filter(|meta| meta.path().is_ident(constants::CFG_IDENT))
+                .collect();
+
+            (!cfgs.is_empty()).then(|| syn::parse_quote! { #[cfg_attr(#predicate, #(#cfgs),*)] })
+        })
+        .collect()
+}
@@ -0,0 +1,90 @@
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, DeriveInput, Ident};
+
+use crate::{
+    constants::{self, AllocCrate, AnyTraitPath, CloneTraitPath, OnceLockPath},
+    traits::Respan,
+    Span2, TokenStream2,
+};
+
+/// Checks that the `cached` option can be used on `input`.
+pub fn check(input: &DeriveInput, cached: &Ident, error_tokens: &mut Vec<TokenStream2>) -> bool {
+    if !cfg!(feature = "std") {
+        error!(
+            error_tokens,
+            cached.span(),
+            "`{}` requires the `std` feature of better_default.",
+            constants::CACHED_OPTION
+        );
+
+        return false;
+    }
+
+    if !input.generics.params.is_empty() {
+        error!(
+            error_tokens,
+            input.generics.span(),
+            "`{}` can't be used on generic types, since the cached value is stored in a static.",
+            constants::CACHED_OPTION
+        );
+
+        return false;
+    }
+
+    true
+}
+
+/// Generates `default_ref`, which computes the default value once with `body` and stores it in a static.
+pub fn default_ref(
+    ident: &Ident,
+    vis: &syn::Visibility,
+    cached: &Ident,
+    body: TokenStream2,
+) -> TokenStream2 {
+    let value = format_ident!("DEFAULT", span = Span2::mixed_site());
+    let init = format_ident!("init", span = Span2::mixed_site());
+    let cell = format_ident!("cell", span = Span2::mixed_site());
+
+    // the static holds a trait object, so the bounds are only required by the helper, and reported on the option
+    let stored = quote! { &'static (dyn #AnyTraitPath + ::core::marker::Send + ::core::marker::Sync) };
+    let helper = quote_spanned! { cached.span() =>
+        fn cached_default_requires_send_sync<T: ::core::marker::Send + ::core::marker::Sync + 'static>(
+            #cell: &'static #OnceLockPath<#stored>,
+            #init: impl FnOnce() -> T,
+        ) -> &'static T {
+            let value = #cell.get_or_init(|| {
+                #AllocCrate::boxed::Box::leak(#AllocCrate::boxed::Box::new(#init()))
+            });
+            // the stored value is always a `T`
+            value.downcast_ref().unwrap()
+        }
+    };
+    let call = quote_spanned! { cached.span() =>
+        cached_default_requires_send_sync(&#value, || -> #ident { #body })
+    };
+
+    quote! {
+        /// Returns a reference to the default value, which is computed once, then shared.
+        #vis fn default_ref() -> &'static Self {
+            #helper
+
+            static #value: #OnceLockPath<#stored> = #OnceLockPath::new();
+            #call
+        }
+    }
+}
+
+/// The body of `default`, cloning the cached default value.
+pub fn default_body(cached: &Ident) -> TokenStream2 {
+    let clone = CloneTraitPath.respanned(cached.span());
+
+    quote_spanned! { cached.span() =>
+        {
+            fn cached_default_requires_clone<T: #clone>(value: &T) -> T {
+                #clone::clone(value)
+            }
+
+            cached_default_requires_clone(Self::default_ref())
+        }
+    }
+}
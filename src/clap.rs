@@ -0,0 +1,275 @@
+use quote::quote;
+use syn::{
+    parse_quote_spanned, spanned::Spanned, Attribute, Data, DeriveInput, Expr, Field, Fields,
+    Ident, Type,
+};
+
+use crate::{
+    attrs, constants,
+    default::{self, DefaultValue, Selection},
+    options::ContainerOptions,
+    profile::Profiled,
+    top_attribute::{self, Location},
+    types, Span2, TokenStream2,
+};
+
+const ARG_IDENT: &str = "arg";
+const COMMAND_IDENT: &str = "command";
+
+/// The `arg` options after which clap already knows the default value of the argument.
+const DEFAULT_ARG_OPTIONS: &[&str] = &[
+    "skip",
+    "default_value",
+    "default_value_t",
+    "default_values",
+    "default_values_t",
+    "default_value_os",
+    "default_value_os_t",
+    "default_values_os",
+    "default_values_os_t",
+];
+
+/// The types whose default value clap converts to an `OsString`, rather than displaying it.
+const OS_STRING_TYPES: &[&str] = &["PathBuf", "OsString"];
+
+/// Checks if `ty` is syntactically a path ending with one of `idents`, like `std::vec::Vec<u8>` for `Vec`.
+fn is_one_of(ty: &Type, idents: &[&str]) -> bool {
+    types::last_segment(ty).is_some_and(|segment| idents.iter().any(|ident| segment.ident == ident))
+}
+
+/// Checks if `expr` is a call to a path ending with `ident`, like `Some(5)`, returning its single argument.
+fn call_argument<'a>(expr: &'a Expr, ident: &str) -> Option<&'a Expr> {
+    match expr {
+        Expr::Call(call) if call.args.len() == 1 => match &*call.func {
+            Expr::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == ident) => {
+                call.args.first()
+            }
+            _ => None,
+        },
+        Expr::Paren(paren) => call_argument(&paren.expr, ident),
+        Expr::Group(group) => call_argument(&group.expr, ident),
+        _ => None,
+    }
+}
+
+/// Checks if the `arg` attribute `attr` has one of the `options`.
+fn has_arg_option(attr: &Attribute, options: &[&str]) -> bool {
+    let mut found = false;
+
+    // the parse result is ignored since clap will report the malformed attributes itself
+    let _ = attr.parse_nested_meta(|meta| {
+        if meta
+            .path
+            .get_ident()
+            .is_some_and(|ident| options.iter().any(|option| ident == option))
+        {
+            found = true;
+        }
+
+        if meta.input.peek(syn::Token![=]) {
+            meta.value()?.parse::<Expr>()?;
+        }
+
+        Ok(())
+    });
+
+    found
+}
+
+fn has_field_arg_option(field: &Field, options: &[&str]) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident(ARG_IDENT) && has_arg_option(attr, options))
+}
+
+fn accepts_default_value(field: &Field) -> bool {
+    // flattened args or subcommands are not arguments at all
+    !field.attrs.iter().any(|attr| attr.path().is_ident(COMMAND_IDENT))
+        && !has_field_arg_option(field, DEFAULT_ARG_OPTIONS)
+}
+
+/// Gives clap the value of an `Option` field written `Some(<value>)`, since clap takes the value of the argument
+/// rather than the one of the field. The value is displayed once, like clap does for `default_value_t`.
+fn optional_default_value(
+    field: &mut Field,
+    value: DefaultValue,
+    error_tokens: &mut Vec<TokenStream2>,
+) {
+    let Ok(expr) = syn::parse2::<Expr>(value.value) else {
+        return;
+    };
+
+    // `None` is the value clap gives to a missing optional argument
+    if matches!(&expr, Expr::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "None")) {
+        return;
+    }
+
+    let inner_ty = types::first_type_argument(&field.ty);
+    let (Some(inner), Some(inner_ty)) = (call_argument(&expr, "Some"), inner_ty) else {
+        error!(
+            error_tokens,
+            field.ty.span(),
+            "clap only takes the default value of an `Option` field written `Some(<value>)` or `None`."
+        );
+        return;
+    };
+
+    if is_one_of(inner_ty, &["Vec"]) {
+        error!(
+            error_tokens,
+            field.ty.span(),
+            "clap can't take the default value of an `Option<Vec<_>>` field, use a `Vec` field instead."
+        );
+        return;
+    }
+
+    let (option, string_ty, to_string, as_str) = if is_one_of(inner_ty, OS_STRING_TYPES) {
+        (
+            quote! { default_value_os },
+            quote! { ::std::ffi::OsString },
+            quote! { ::std::ffi::OsString::from },
+            quote! { as_os_str },
+        )
+    } else {
+        (
+            quote! { default_value },
+            quote! { ::std::string::String },
+            quote! { ::std::string::ToString::to_string },
+            quote! { as_str },
+        )
+    };
+
+    let attr = parse_quote_spanned! { field.ty.span()=>
+        #[arg(#option = {
+            static VALUE: ::std::sync::OnceLock<#string_ty> = ::std::sync::OnceLock::new();
+            VALUE.get_or_init(|| { let value: #inner_ty = #inner; #to_string(&value) }).#as_str()
+        })]
+    };
+
+    field.attrs.push(attr);
+}
+
+fn add_default_values(
+    top_attribute: Option<&Attribute>,
+    fields: &mut Fields,
+    error_tokens: &mut Vec<TokenStream2>,
+) {
+    // the errors are already reported by the `Default` derive
+    let mut ignored_errors = Vec::new();
+
+    let mut top_default_values = Profiled::default();
+    if let Some(top_attribute) = top_attribute.and_then(|attr| {
+        top_attribute::get_default_values(
+            attr,
+            fields,
+            Location::Struct,
+            false,
+            &mut ignored_errors,
+        )
+    }) {
+        top_default_values.insert(None, top_attribute.values);
+    }
+
+    let default_values = default::get_field_default_values(
+        &top_default_values,
+        fields,
+        None,
+        &ContainerOptions::default(),
+        &mut ignored_errors,
+    );
+
+    for (field, default_values) in fields.iter_mut().zip(default_values) {
+        if !accepts_default_value(field) {
+            continue;
+        }
+
+        let value = default_values.get(Selection::default());
+        if is_one_of(&field.ty, &["Option"]) {
+            // a missing optional argument is `None`, like the default value of the type
+            if value.is_override {
+                optional_default_value(field, value, error_tokens);
+            }
+            continue;
+        }
+
+        let is_many = is_one_of(&field.ty, &["Vec"]);
+        let value_ty = if is_many { types::first_type_argument(&field.ty) } else { Some(&field.ty) };
+        let is_os = value_ty.is_some_and(|ty| is_one_of(ty, OS_STRING_TYPES));
+
+        let option = match (is_many, is_os) {
+            (false, false) => "default_value_t",
+            (true, false) => "default_values_t",
+            (false, true) => "default_value_os_t",
+            (true, true) => "default_values_os_t",
+        };
+        let option = Ident::new(option, Span2::call_site());
+
+        // the block prevents clap from parsing only the leading literal of expressions like `"a".to_string()`,
+        // and gives the value the field type, which clap doesn't know.
+        // It's spanned at the type, so the compiler reports the types clap can't display on the field
+        let ty = &field.ty;
+        let default = value.value;
+        let attr = parse_quote_spanned! { ty.span()=> #[arg(#option = { let value: #ty = #default; value })] };
+        field.attrs.push(attr);
+
+        // the default parser of `PathBuf` rejects the empty path, the default value of the type
+        if !value.is_override
+            && !is_many
+            && is_one_of(ty, &["PathBuf"])
+            && !has_field_arg_option(field, &["value_parser"])
+        {
+            field.attrs.push(parse_quote_spanned! { ty.span()=>
+                #[arg(value_parser = ::clap::builder::TypedValueParser::map(
+                    ::clap::builder::OsStringValueParser::new(),
+                    ::std::path::PathBuf::from,
+                ))]
+            });
+        }
+    }
+}
+
+pub fn expand(args: &TokenStream2, mut input: DeriveInput) -> TokenStream2 {
+    let mut error_tokens = Vec::new();
+
+    if !args.is_empty() {
+        error!(
+            error_tokens,
+            args.span(),
+            "this attribute doesn't take any arguments."
+        );
+    }
+
+    match &mut input.data {
+        Data::Struct(data) => {
+            // clap only knows the unqualified default values
+            let top_attribute = attrs::find_attribute_unique_per_profile(
+                &input.attrs,
+                constants::DEFAULT_IDENT,
+                &mut Vec::new(),
+            )
+            .into_iter()
+            .find_map(|(profile, attr)| profile.is_none().then_some(attr));
+
+            add_default_values(top_attribute, &mut data.fields, &mut error_tokens);
+        }
+        Data::Enum(data) => error!(
+            error_tokens,
+            data.enum_token.span(),
+            "this attribute is only implemented for structs."
+        ),
+        Data::Union(data) => error!(
+            error_tokens,
+            data.union_token.span(),
+            "this attribute is only implemented for structs."
+        ),
+    }
+
+    let error_tokens: TokenStream2 = error_tokens.into_iter().collect();
+
+    quote! {
+        #input
+
+        #error_tokens
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Expr, ExprArray, Ident, Lit, Token, Type,
+};
+
+use crate::{
+    constants::{CollectPath, FromIteratorTraitPath, IntoIteratorTraitPath, IntoTraitPath, PhantomDataPath},
+    traits::Respan,
+    types, Span2, TokenStream2,
+};
+
+/// An entry of a map literal : `<key>: <value>`.
+#[derive(Clone)]
+struct MapEntry {
+    key: Expr,
+    _colon: Token![:],
+    value: Expr,
+}
+
+impl Parse for MapEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            key: input.parse()?,
+            _colon: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+/// A map literal : `{<key>: <value>, ...}`, collected into any map-like type.
+#[derive(Clone)]
+pub struct MapLiteral {
+    brace: token::Brace,
+    entries: Punctuated<MapEntry, Token![,]>,
+}
+
+impl MapLiteral {
+    /// Parses a map literal if `input` starts with one, leaving the blocks to be parsed as expressions.
+    pub fn parse_if_present(input: ParseStream) -> syn::Result<Option<Self>> {
+        if !input.peek(token::Brace) {
+            return Ok(None);
+        }
+
+        let fork = input.fork();
+        let is_map = (|| {
+            let content;
+            syn::braced!(content in fork);
+            content.parse::<Expr>()?;
+            content.parse::<Token![:]>()?;
+
+            Ok::<_, syn::Error>(())
+        })()
+        .is_ok();
+
+        if !is_map {
+            return Ok(None);
+        }
+
+        let content;
+        let brace = syn::braced!(content in input);
+        let entries = Punctuated::<MapEntry, Token![,]>::parse_terminated(&content)?;
+
+        let mut keys = HashSet::new();
+        for entry in &entries {
+            if !keys.insert(entry.key.to_token_stream().to_string()) {
+                return Err(error!(entry.key.span(), "this key is already declared."));
+            }
+        }
+
+        Ok(Some(Self { brace, entries }))
+    }
+
+    pub fn span(&self) -> Span2 {
+        self.brace.span.join()
+    }
+
+    pub fn collect(&self) -> TokenStream2 {
+        let entries = self.entries.iter().map(|entry| {
+            let key = convert(&entry.key);
+            let value = convert(&entry.value);
+
+            quote_spanned! { entry.key.span()=> (#key, #value) }
+        });
+
+        collect(quote! { [#(#entries),*] }, self.span())
+    }
+}
+
+/// Collects the array `elems` into the collection, with the span of the literal.
+fn collect(elems: TokenStream2, span: Span2) -> TokenStream2 {
+    let collect = CollectPath.respanned(span);
+    let into_iter = quote! { #IntoIteratorTraitPath::into_iter }.respanned(span);
+
+    quote_spanned! { span=> #collect(#into_iter(#elems)) }
+}
+
+/// Converts an element with `Into`, except the number literals, whose type is inferred from the collection.
+fn convert(expr: &Expr) -> TokenStream2 {
+    let literal = match expr {
+        Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Neg(_)) => &*unary.expr,
+        _ => expr,
+    };
+
+    match literal {
+        Expr::Lit(lit) if matches!(lit.lit, Lit::Int(_) | Lit::Float(_)) => expr.to_token_stream(),
+        _ => {
+            let into = quote! { #IntoTraitPath::into }.respanned(expr.span());
+            quote_spanned! { expr.span()=> #into(#expr) }
+        }
+    }
+}
+
+/// Builds an array literal into the type `ty`.
+///
+/// The literal is collected with `FromIterator`, unless `ty` is an array type, which can be hidden by an alias,
+/// so the compiler chooses between the two with the type : the method `build` of `KeepArray` only applies to
+/// the arrays, and is found before the one of `CollectArray`, which needs one more reference.
+pub fn build_array(array: &ExprArray, ty: &Type) -> TokenStream2 {
+    let span = array.bracket_token.span.join();
+    let elems = array.elems.iter().map(convert);
+    let binding = format_ident!("elems", span = Span2::mixed_site());
+    let collect = collect(quote! { #binding }, span);
+
+    quote_spanned! { span=>
+        match [#(#elems),*] {
+            #binding => {
+                struct ArrayTarget<T>(#PhantomDataPath<T>);
+
+                // only one of the traits is used
+                #[allow(dead_code)]
+                trait KeepArray<T, const N: usize> {
+                    fn build(&self, #binding: [T; N]) -> [T; N] {
+                        #binding
+                    }
+                }
+                impl<T, const N: usize> KeepArray<T, N> for ArrayTarget<[T; N]> {}
+
+                #[allow(dead_code)]
+                trait CollectArray<C> {
+                    fn build<I, const N: usize>(&self, #binding: [I; N]) -> C
+                    where
+                        C: #FromIteratorTraitPath<I>,
+                    {
+                        #collect
+                    }
+                }
+                impl<C> CollectArray<C> for &ArrayTarget<C> {}
+
+                (&ArrayTarget::<#ty>(#PhantomDataPath)).build(#binding)
+            }
+        }
+    }
+}
+
+/// Whether an array literal is built into the field of type `ty` whose value is wrapped with `modifiers`,
+/// returning the wrapped type.
+///
+/// The type wrapped by the modifiers is unknown after an `into`, and an array type needs nothing,
+/// so the array is left as it is.
+pub fn array_target<'a>(ty: &'a Type, modifiers: &[Ident]) -> Option<&'a Type> {
+    types::wrapped_type(ty, modifiers).filter(|ty| !matches!(types::strip(ty), Type::Array(_)))
+}
@@ -0,0 +1,165 @@
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{parse::ParseStream, spanned::Spanned, Attribute, Expr, Ident, Meta, Token};
+
+use crate::{
+    constants::{self, CompileErrorPath},
+    default::Selection,
+    derive::DefaultFields,
+    traits::Respan,
+    Span2, TokenStream2,
+};
+
+/// The condition selecting a default variant of an enum.
+pub enum Condition {
+    /// `cfg(<predicate>)` : the variant is the default one in the configurations matching the predicate.
+    Cfg(Meta),
+    /// `when = <expression>` : the variant is the default one when the expression is `true` at runtime.
+    When(Expr),
+}
+
+impl Condition {
+    pub fn span(&self) -> Span2 {
+        match self {
+            Self::Cfg(predicate) => predicate.span(),
+            Self::When(when) => when.span(),
+        }
+    }
+}
+
+/// Parses `cfg(<predicate>)`, returning the predicate.
+pub fn parse_cfg(input: ParseStream) -> syn::Result<Meta> {
+    let ident: Ident = input.parse()?;
+    if ident != constants::CFG_IDENT {
+        return Err(error!(ident.span(), "expected `{}`.", constants::CFG_IDENT));
+    }
+
+    let content;
+    syn::parenthesized!(content in input);
+    let predicate = content.parse()?;
+    if !content.is_empty() {
+        return Err(content.error("expected a single `cfg` predicate."));
+    }
+
+    Ok(predicate)
+}
+
+/// Whether `input` starts with a condition : `cfg` followed by parentheses, or `when =`.
+fn peek_condition(input: ParseStream) -> bool {
+    let fork = input.fork();
+    let Ok(ident) = fork.parse::<Ident>() else {
+        return false;
+    };
+
+    (ident == constants::CFG_IDENT && fork.peek(syn::token::Paren))
+        || (ident == constants::WHEN_IDENT && fork.peek(Token![=]) && !fork.peek(Token![==]))
+}
+
+/// Parses the `cfg(<predicate>)` or `when = <expression>` header of a default attribute, if there is one.
+///
+/// The separator following the header is left in the stream.
+pub fn parse_condition(input: ParseStream) -> syn::Result<Option<Condition>> {
+    if !peek_condition(input) {
+        return Ok(None);
+    }
+
+    // `cfg` is followed by its predicate, and `when` by `=`
+    if input.peek2(syn::token::Paren) {
+        return Ok(Some(Condition::Cfg(parse_cfg(input)?)));
+    }
+
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+
+    Ok(Some(Condition::When(input.parse()?)))
+}
+
+/// Whether a default attribute starts with a condition, without checking the rest of the attribute.
+pub fn has_condition(attr: &Attribute) -> bool {
+    let syn::Meta::List(list) = &attr.meta else {
+        return false;
+    };
+
+    list.parse_args_with(|input: ParseStream| {
+        let has_condition = peek_condition(input);
+        input.parse::<TokenStream2>()?;
+
+        Ok(has_condition)
+    })
+    .unwrap_or(false)
+}
+
+/// A default variant selected by a condition.
+pub struct ConditionalDefault<'a> {
+    pub ident: Ident,
+    pub condition: Condition,
+    pub fields: DefaultFields<'a>,
+}
+
+/// A `fn default()` of the `Default` implementation : the `cfg` attribute guarding it, and its body.
+pub struct Branch {
+    pub cfg: Option<TokenStream2>,
+    pub body: TokenStream2,
+}
+
+/// Chains the `when` conditions, checked in declaration order, before `otherwise`.
+fn chain(whens: &[(&Expr, TokenStream2)], otherwise: TokenStream2) -> TokenStream2 {
+    whens.iter().rev().fold(otherwise, |otherwise, (when, body)| {
+        quote! {
+            if #when {
+                #body
+            } else {
+                #otherwise
+            }
+        }
+    })
+}
+
+/// Splits the `Default` implementation into a branch per `cfg` condition, each one excluding the previous ones,
+/// so the first matching variant in declaration order is selected.
+///
+/// The last branch holds the configurations matching none of the `cfg` conditions :
+/// it returns `fallback`, the unconditional default variant, or fails to compile without one.
+pub fn branches(
+    enum_ident: &Ident,
+    conditional: &[ConditionalDefault],
+    fallback: Option<TokenStream2>,
+) -> Vec<Branch> {
+    let mut branches = Vec::new();
+    let mut cfgs = Vec::new();
+    let mut whens = Vec::new();
+
+    for ConditionalDefault {
+        condition, fields, ..
+    } in conditional
+    {
+        let body = fields.body(Selection::default());
+
+        match condition {
+            Condition::Cfg(predicate) => {
+                branches.push(Branch {
+                    cfg: Some(quote! { #[cfg(all(#predicate, not(any(#(#cfgs),*))))] }),
+                    body: chain(&whens, body),
+                });
+                cfgs.push(predicate.to_token_stream());
+            }
+            Condition::When(when) => whens.push((when, body)),
+        }
+    }
+
+    let fallback = fallback.unwrap_or_else(|| {
+        let message = format!(
+            "no default variant of `{}` matches this configuration, add an unconditional `#[{}]` variant.",
+            enum_ident,
+            constants::DEFAULT_IDENT
+        );
+
+        let compile_error = CompileErrorPath.respanned(enum_ident.span());
+        quote_spanned! { enum_ident.span()=> #compile_error!(#message) }
+    });
+    branches.push(Branch {
+        cfg: (!cfgs.is_empty()).then(|| quote! { #[cfg(not(any(#(#cfgs),*)))] }),
+        body: chain(&whens, fallback),
+    });
+
+    branches
+}
@@ -1,6 +1,49 @@
 use quote::quote;
 
 pub const DEFAULT_IDENT: &str = "default";
+pub const DEFAULT_WITH_IDENT: &str = "default_with";
+pub const PROFILE_IDENT: &str = "profile";
+pub const EACH_IDENT: &str = "each";
+pub const PARSE_IDENT: &str = "parse";
+pub const DURATION_IDENT: &str = "duration";
+pub const BYTES_IDENT: &str = "bytes";
+pub const AUTO_IDENT: &str = "auto";
+pub const IN_PLACE_IDENT: &str = "in_place";
+pub const RANGE_IDENT: &str = "range";
+pub const CFG_IDENT: &str = "cfg";
+pub const WHEN_IDENT: &str = "when";
+pub const SINCE_IDENT: &str = "since";
+
+pub const INTO_MODIFIER: &str = "into";
+pub const SOME_MODIFIER: &str = "some";
+pub const BOXED_MODIFIER: &str = "boxed";
+pub const ARC_MODIFIER: &str = "arc";
+pub const RC_MODIFIER: &str = "rc";
+pub const MODIFIERS: &[&str] = &[
+    INTO_MODIFIER,
+    SOME_MODIFIER,
+    BOXED_MODIFIER,
+    ARC_MODIFIER,
+    RC_MODIFIER,
+];
+
+pub const ERROR_OPTION: &str = "error";
+pub const FIELD_ERROR_OPTION: &str = "field_error";
+pub const IMPL_DEFAULT_OPTION: &str = "impl_default";
+pub const TRACE_PANICS_OPTION: &str = "trace_panics";
+pub const AUTO_OPTION: &str = AUTO_IDENT;
+pub const ZEROED_OPTION: &str = "zeroed";
+pub const IN_PLACE_OPTION: &str = IN_PLACE_IDENT;
+pub const CACHED_OPTION: &str = "cached";
+pub const INTO_ALL_OPTION: &str = "into_all";
+pub const VALIDATE_OPTION: &str = "validate";
+pub const ALLOW_OPTION: &str = "allow";
+pub const VARIANTS_OPTION: &str = "variants";
+
+pub const RECURSION_LINT: &str = "recursion";
+pub const REDUNDANT_LINT: &str = "redundant";
+pub const REDUNDANT_PROFILE_LINT: &str = "redundant_profile";
+pub const LINTS: &[&str] = &[RECURSION_LINT, REDUNDANT_LINT, REDUNDANT_PROFILE_LINT];
 
 macro_rules! create_const_tokens {
     ($ident: ident = $($tt: tt)*) => {
@@ -14,3 +57,36 @@ macro_rules! create_const_tokens {
 }
 
 create_const_tokens!(DefaultTraitPath = core::default::Default);
+create_const_tokens!(ArrayFromFnPath = core::array::from_fn);
+create_const_tokens!(NullPath = core::ptr::null);
+create_const_tokens!(NullMutPath = core::ptr::null_mut);
+create_const_tokens!(NonePath = core::option::Option::None);
+create_const_tokens!(ZeroedPath = core::mem::zeroed);
+create_const_tokens!(PtrWritePath = core::ptr::write);
+create_const_tokens!(AddrOfMutPath = core::ptr::addr_of_mut);
+create_const_tokens!(WriteBytesPath = core::ptr::write_bytes);
+create_const_tokens!(CloneTraitPath = core::clone::Clone);
+create_const_tokens!(OnceLockPath = std::sync::OnceLock);
+create_const_tokens!(AnyTraitPath = core::any::Any);
+create_const_tokens!(IntoTraitPath = core::convert::Into);
+create_const_tokens!(SomePath = core::option::Option::Some);
+create_const_tokens!(IntoIteratorTraitPath = core::iter::IntoIterator);
+create_const_tokens!(CollectPath = core::iter::Iterator::collect);
+create_const_tokens!(FromIteratorTraitPath = core::iter::FromIterator);
+create_const_tokens!(PhantomDataPath = core::marker::PhantomData);
+create_const_tokens!(FromStrTraitPath = core::str::FromStr);
+create_const_tokens!(DurationPath = core::time::Duration);
+create_const_tokens!(CompileErrorPath = core::compile_error);
+
+/// The crate providing the allocation types : `std`, or `alloc` without the `std` feature.
+pub struct AllocCrate;
+
+impl quote::ToTokens for AllocCrate {
+    fn to_tokens(&self, tokens: &mut crate::TokenStream2) {
+        if cfg!(feature = "std") {
+            quote! { std }.to_tokens(tokens)
+        } else {
+            quote! { alloc }.to_tokens(tokens)
+        }
+    }
+}
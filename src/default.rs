@@ -1,31 +1,738 @@
 use std::collections::HashMap;
 
-use quote::{quote, ToTokens};
-use syn::{spanned::Spanned, Expr, Fields, Ident, Token};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
+use syn::{parse::Parse, spanned::Spanned, Attribute, Expr, ExprRange, Field, Fields, Ident, LitInt, LitStr, Meta, Token, Type};
 
-use crate::{attrs, constants::{self, DefaultTraitPath}, traits::JoinTokens, Span2, TokenStream2};
+use crate::{
+    attrs,
+    collection::{self, MapLiteral},
+    condition,
+    constants::{self, AllocCrate, ArrayFromFnPath, DefaultTraitPath, IntoTraitPath, SomePath},
+    options::ContainerOptions,
+    lints, parsed, profile::{self, Profiled}, recursion,
+    traits::Respan,
+    types, units, validate,
+    versions::{self, VersionedValue},
+    Span2, TokenStream2,
+};
 
 pub struct DefaultValue {
     ident: Option<Ident>,
-    value: TokenStream2,
+    /// The name of the field : its ident, or its position for unnamed fields.
+    pub name: String,
+    pub ty: Type,
+    pub value: TokenStream2,
+    /// Whether the value comes from an attribute, rather than being the default value of the field type.
+    pub is_override: bool,
+    /// Whether the default value of the field type is its `auto` default value.
+    pub auto: bool,
+    /// Whether the field is written in place with the `default_in_place` function of its own type.
+    pub in_place: bool,
+    /// The `cfg` attributes of the field, put on everything generated for it.
+    pub cfgs: Vec<Attribute>,
+}
+
+impl DefaultValue {
+    /// The member used to access the field : its ident, or its index for unnamed fields.
+    pub fn member(&self) -> syn::Member {
+        match &self.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(self.name.parse::<usize>().unwrap())),
+        }
+    }
 }
 
 impl ToTokens for DefaultValue {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(&self.cfgs);
+
         if let Some(ident) = &self.ident {
             ident.to_tokens(tokens);
-            Token![:](Span2::call_site()).to_tokens(tokens);
+            Token![:](ident.span()).to_tokens(tokens);
         }
 
         self.value.to_tokens(tokens);
     }
 }
 
-fn get_field_default_values(
-    top_default_values: Option<&HashMap<String, Expr>>,
+/// A default value : `<expression>`, or `<context> => <expression>` when it uses the context of the `DefaultWith` derive.
+///
+/// The expression can also be `each = <closure>`, building the elements of an array from their index,
+/// or be preceded by modifiers wrapping it, like `some <expression>`.
+///
+/// Instead of an expression, the value can be a string : `parse = "<string>"`, parsed into the field type,
+/// `duration = "<duration>"` or `bytes = "<size>"`.
+///
+/// The value can be preceded by values for some configurations : `cfg(<predicate>) = <value>, ..., <fallback>`.
+#[derive(Clone)]
+pub struct DefaultExpr {
+    context: Option<Ident>,
+    each: Option<Ident>,
+    modifiers: Vec<Ident>,
+    value: Value,
+    /// `range = <range>` : the range the value must be in, only after the value of a field attribute.
+    range: Option<ExprRange>,
+    /// `cfg(<predicate>) = <value>, ...` before the value : the values replacing it in the configurations
+    /// matching their predicate, the first matching one being used.
+    alternatives: Vec<CfgAlternative>,
+}
+
+/// A default value only used in the configurations matching a predicate : `cfg(<predicate>) = <value>`.
+#[derive(Clone)]
+struct CfgAlternative {
+    predicate: Meta,
+    value: DefaultExpr,
+}
+
+#[derive(Clone)]
+enum Value {
+    Expr(Box<Expr>),
+    /// `{<key>: <value>, ...}`
+    Map(MapLiteral),
+    /// `parse = "<string>"`
+    Parse(LitStr),
+    /// `duration = "<duration>"`
+    Duration(LitStr),
+    /// `bytes = "<size>"`
+    Bytes(LitStr),
+}
+
+/// Whether `input` starts with a modifier, followed by the expression it wraps.
+///
+/// The next token must start an expression on its own, and can't continue a binary expression or a path,
+/// so that `some(5)`, `into.x` or `some - 1` are left as they are.
+fn peek_modifier(input: syn::parse::ParseStream) -> bool {
+    let fork = input.fork();
+    let is_modifier = fork
+        .parse::<Ident>()
+        .is_ok_and(|ident| constants::MODIFIERS.contains(&ident.to_string().as_str()));
+
+    // the negative literals are parsed as literals, but can be a subtraction
+    is_modifier
+        && !fork.peek(Token![-])
+        && (fork.peek(syn::Lit)
+            || fork.peek(Ident)
+            || fork.peek(Token![Self])
+            || fork.peek(Token![self])
+            || fork.peek(Token![crate])
+            || fork.peek(syn::token::Brace)
+            || fork.peek(syn::token::Bracket))
+}
+
+/// Parses `<keyword> =` if `input` starts with it.
+pub fn parse_keyword(input: syn::parse::ParseStream, keyword: &str) -> syn::Result<Option<Ident>> {
+    let fork = input.fork();
+    let is_keyword = fork.parse::<Ident>().is_ok_and(|ident| ident == keyword)
+        && fork.peek(Token![=])
+        && !fork.peek(Token![==]);
+
+    if !is_keyword {
+        return Ok(None);
+    }
+
+    let ident = input.parse()?;
+    input.parse::<Token![=]>()?;
+
+    Ok(Some(ident))
+}
+
+/// Whether `input` starts with the key of a `cfg` alternative : `cfg(<predicate>) =`.
+fn peek_cfg_key(input: syn::parse::ParseStream) -> bool {
+    let fork = input.fork();
+
+    fork.parse::<Ident>()
+        .is_ok_and(|ident| ident == constants::CFG_IDENT)
+        && fork.parse::<proc_macro2::Group>().is_ok()
+        && fork.peek(Token![=])
+        && !fork.peek(Token![==])
+}
+
+impl Parse for DefaultExpr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut alternatives = Vec::new();
+        while peek_cfg_key(input) {
+            let predicate = condition::parse_cfg(input)?;
+            input.parse::<Token![=]>()?;
+            alternatives.push(CfgAlternative {
+                predicate,
+                value: Self::parse_value(input)?,
+            });
+
+            if input.parse::<Option<Token![,]>>()?.is_none() || input.is_empty() {
+                return Err(input.error(format!(
+                    "expected the fallback value, used when no predicate holds : `{}(<predicate>) = <value>, <fallback>`.",
+                    constants::CFG_IDENT
+                )));
+            }
+        }
+
+        let mut value = Self::parse_value(input)?;
+        value.alternatives = alternatives;
+
+        Ok(value)
+    }
+}
+
+impl DefaultExpr {
+    /// Parses a value without its `cfg` alternatives.
+    fn parse_value(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let context = if input.peek(Ident) && input.peek2(Token![=>]) {
+            let context = input.parse()?;
+            input.parse::<Token![=>]>()?;
+
+            Some(context)
+        } else {
+            None
+        };
+
+        let each = parse_keyword(input, constants::EACH_IDENT)?;
+        if each.is_some() {
+            return Ok(Self {
+                context,
+                each,
+                modifiers: Vec::new(),
+                value: Value::Expr(input.parse()?),
+                range: None,
+                alternatives: Vec::new(),
+            });
+        }
+
+        let mut modifiers = Vec::new();
+        while peek_modifier(input) {
+            modifiers.push(input.parse()?);
+        }
+
+        let value = if parse_keyword(input, constants::PARSE_IDENT)?.is_some() {
+            Value::Parse(input.parse()?)
+        } else if parse_keyword(input, constants::DURATION_IDENT)?.is_some() {
+            Value::Duration(input.parse()?)
+        } else if parse_keyword(input, constants::BYTES_IDENT)?.is_some() {
+            Value::Bytes(input.parse()?)
+        } else if let Some(map) = MapLiteral::parse_if_present(input)? {
+            Value::Map(map)
+        } else {
+            Value::Expr(input.parse()?)
+        };
+
+        Ok(Self {
+            context,
+            each,
+            modifiers,
+            value,
+            range: None,
+            alternatives: Vec::new(),
+        })
+    }
+}
+
+impl Value {
+    fn span(&self) -> Span2 {
+        match self {
+            Self::Expr(expr) => expr.span(),
+            Self::Map(map) => map.span(),
+            Self::Parse(lit) | Self::Duration(lit) | Self::Bytes(lit) => lit.span(),
+        }
+    }
+}
+
+/// The value of a string which can't be parsed, keeping the generated code valid beside the reported error.
+fn placeholder() -> TokenStream2 {
+    quote! { <_ as #DefaultTraitPath>::default() }
+}
+
+impl DefaultExpr {
+    /// Whether this value, or one of its alternatives, uses the context.
+    fn uses_context(&self) -> bool {
+        self.context.is_some() || self.alternatives.iter().any(|a| a.value.context.is_some())
+    }
+
+    /// The values of the alternatives, then this value, used when no predicate holds.
+    fn branches(&self) -> impl Iterator<Item = &DefaultExpr> {
+        self.alternatives.iter().map(|a| &a.value).chain([self])
+    }
+
+    /// Gets the expression for the field `name` of type `ty`, with its modifiers applied,
+    /// and converted with `Into` if `into_all` is set.
+    ///
+    /// With alternatives, the values are in blocks gated by `cfg` attributes, each one excluding the previous predicates,
+    /// so only the value of the current configuration is type-checked.
+    pub fn tokens(&self, name: &str, ty: &Type, into_all: bool) -> TokenStream2 {
+        let tokens = self.value_tokens(name, ty, into_all);
+        if self.alternatives.is_empty() {
+            return tokens;
+        }
+
+        let mut predicates = Vec::new();
+        let mut blocks = Vec::new();
+        for CfgAlternative { predicate, value } in &self.alternatives {
+            let value = value.value_tokens(name, ty, into_all);
+            blocks.push(quote! { #[cfg(all(#predicate, not(any(#(#predicates),*))))] { #value } });
+            predicates.push(predicate);
+        }
+
+        quote! {
+            {
+                #(#blocks)*
+                #[cfg(not(any(#(#predicates),*)))] { #tokens }
+            }
+        }
+    }
+
+    /// Gets the expression of this value, without its alternatives.
+    fn value_tokens(&self, name: &str, ty: &Type, into_all: bool) -> TokenStream2 {
+        let (mut tokens, is_typed) = match &self.value {
+            Value::Expr(value) if self.each.is_some() => {
+                // the call spans from `each` to the end of the closure
+                let span = self.each.as_ref().unwrap().span();
+                let end = value.to_token_stream().into_iter().last().map_or(span, |t| t.span());
+                let from_fn = ArrayFromFnPath.respanned(span);
+                let mut args = proc_macro2::Group::new(
+                    proc_macro2::Delimiter::Parenthesis,
+                    value.to_token_stream(),
+                );
+                args.set_span(end);
+
+                return quote_spanned! { span=> #from_fn #args };
+            }
+            Value::Expr(value) => match (&**value, collection::array_target(ty, &self.modifiers)) {
+                (Expr::Array(array), Some(target)) => (collection::build_array(array, target), true),
+                (expr, _) => {
+                    let tokens = expr.to_token_stream();
+                    let tokens = match &self.range {
+                        Some(range) => validate::range_tokens(range, expr, tokens, name),
+                        None => tokens,
+                    };
+                    (tokens, false)
+                }
+            },
+            Value::Map(map) => (map.collect(), true),
+            // the errors of the strings are reported by `check`
+            Value::Parse(lit) => {
+                let ty = types::wrapped_type(ty, &self.modifiers).unwrap_or(ty);
+                let value = match parsed::parse(lit, ty) {
+                    Ok(Some(value)) => value,
+                    _ => parsed::from_str(lit, ty, name),
+                };
+                (value.respanned(lit.span()), true)
+            }
+            Value::Duration(lit) => {
+                let value = units::duration(lit).unwrap_or_else(|_| placeholder());
+                (value.respanned(lit.span()), true)
+            }
+            Value::Bytes(lit) => {
+                let ty = types::wrapped_type(ty, &self.modifiers);
+                let value = units::bytes(lit, ty).unwrap_or_else(|_| placeholder());
+                (value.respanned(lit.span()), true)
+            }
+        };
+
+        for modifier in self.modifiers.iter().rev() {
+            let path = match modifier.to_string().as_str() {
+                constants::INTO_MODIFIER => quote! { #IntoTraitPath::into },
+                constants::SOME_MODIFIER => quote! { #SomePath },
+                constants::BOXED_MODIFIER => quote! { #AllocCrate::boxed::Box::new },
+                constants::ARC_MODIFIER => quote! { #AllocCrate::sync::Arc::new },
+                constants::RC_MODIFIER => quote! { #AllocCrate::rc::Rc::new },
+                _ => unreachable!(),
+            }
+            .respanned(modifier.span());
+
+            tokens = quote_spanned! { modifier.span()=> #path(#tokens) };
+        }
+
+        // the values already converted would make the conversion ambiguous,
+        // like the collections, whose elements are already converted
+        if into_all && !is_typed && !self.modifiers.iter().any(|m| m == constants::INTO_MODIFIER)
+        {
+            let span = self.value.span();
+            let into = quote! { #IntoTraitPath::into }.respanned(span);
+            tokens = quote_spanned! { span=> #into(#tokens) };
+        }
+
+        tokens
+    }
+
+    /// Checks that this value and its alternatives can be used on a field of type `ty`,
+    /// in a type declaring the context `context`.
+    pub fn check(&self, ty: &Type, context: Option<&Ident>, error_tokens: &mut Vec<TokenStream2>) {
+        for value in self.branches() {
+            value.check_value(ty, context, error_tokens);
+        }
+    }
+
+    fn check_value(&self, ty: &Type, context: Option<&Ident>, error_tokens: &mut Vec<TokenStream2>) {
+        let wrapped_ty = types::wrapped_type(ty, &self.modifiers);
+        match &self.value {
+            Value::Parse(lit) => match wrapped_ty {
+                Some(ty) => {
+                    handle_error!(parsed::parse(lit, ty), error_tokens);
+                }
+                None => error!(
+                    error_tokens,
+                    lit.span(),
+                    "`{}` can't follow the `{}` modifier, since the type to parse into is unknown.",
+                    constants::PARSE_IDENT,
+                    constants::INTO_MODIFIER
+                ),
+            },
+            Value::Duration(lit) => {
+                handle_error!(units::duration(lit), error_tokens);
+            }
+            Value::Bytes(lit) => {
+                handle_error!(units::bytes(lit, wrapped_ty), error_tokens);
+            }
+            _ => (),
+        }
+
+        if let Some(range) = &self.range {
+            match &self.value {
+                Value::Expr(value) if self.each.is_none() && !matches!(**value, Expr::Array(_)) => {
+                    handle_error!(validate::check_range(range, value), error_tokens);
+                }
+                _ => error!(
+                    error_tokens,
+                    range.span(),
+                    "`{}` can only be used with an expression.",
+                    constants::RANGE_IDENT
+                ),
+            }
+        }
+
+        if let Some(each) = &self.each {
+            if !matches!(types::strip(ty), Type::Array(_)) {
+                error!(
+                    error_tokens,
+                    each.span(),
+                    "`{}` can only be used on fields with an array type.",
+                    constants::EACH_IDENT
+                );
+            }
+        }
+
+        let Some(used) = &self.context else {
+            return;
+        };
+
+        match context {
+            Some(context) if context == used => (),
+            Some(context) => error!(
+                error_tokens,
+                used.span(),
+                "unknown context `{}`, the context declared in the `{}` attribute is `{}`.",
+                used,
+                constants::DEFAULT_WITH_IDENT,
+                context
+            ),
+            None => error!(
+                error_tokens,
+                used.span(),
+                "the context can only be used with the `DefaultWith` derive, which requires a `#[{}(<ident>: <type>)]` attribute.",
+                constants::DEFAULT_WITH_IDENT
+            ),
+        }
+    }
+}
+
+/// The arguments of a per-field attribute : `#[default(<value>)]` or `#[default(profile = "<name>", <value>)]`.
+///
+/// The value can be followed by `range = <range>`, checking that it's in the range.
+///
+/// The value can also be a marker, `auto` or `in_place`, only in an unqualified attribute.
+///
+/// An unprofiled value can be used from a version on : `#[default(since = <version>, <value>)]`.
+struct FieldAttribute {
+    /// The default value, `None` for a marker.
+    value: Option<DefaultExpr>,
+    marker: Option<Ident>,
+    since: Option<LitInt>,
+}
+
+impl Parse for FieldAttribute {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let profile = profile::parse_profile(input)?;
+        if profile.is_some() {
+            input.parse::<Token![,]>()?;
+        }
+
+        let since = versions::parse_since(input)?;
+        if let Some(since) = &since {
+            if profile.is_some() {
+                return Err(error!(
+                    since.span(),
+                    "`{}` can't be used in a profiled attribute.",
+                    constants::SINCE_IDENT
+                ));
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        let fork = input.fork();
+        let is_marker = fork.parse::<Ident>().is_ok_and(|ident| {
+            ident == constants::AUTO_IDENT || ident == constants::IN_PLACE_IDENT
+        }) && fork.is_empty();
+
+        if !is_marker {
+            let mut value: DefaultExpr = input.parse()?;
+            if input.parse::<Option<Token![,]>>()?.is_some() {
+                value.range = validate::parse_range(input)?;
+                if value.range.is_none() && !input.is_empty() {
+                    return Err(input.error(format!(
+                        "expected `{} = <range>`.",
+                        constants::RANGE_IDENT
+                    )));
+                }
+
+                // the range applies to the value of every configuration
+                for alternative in &mut value.alternatives {
+                    alternative.value.range = value.range.clone();
+                }
+            }
+
+            return Ok(Self {
+                value: Some(value),
+                marker: None,
+                since,
+            });
+        }
+
+        let ident: Ident = input.parse()?;
+        if profile.is_some() || since.is_some() {
+            return Err(error!(
+                ident.span(),
+                "`{}` can't be used in a {} attribute.",
+                ident,
+                if since.is_some() { "versioned" } else { "profiled" }
+            ));
+        }
+
+        Ok(Self {
+            value: None,
+            marker: Some(ident),
+            since: None,
+        })
+    }
+}
+
+/// Selects which default values are used to build a body.
+#[derive(Clone, Copy, Default)]
+pub struct Selection<'a> {
+    /// The profile of the values, `None` for the unqualified ones.
+    pub profile: Option<&'a LitStr>,
+    /// The ident of the context parameter, when building the body of `default_with`.
+    pub context: Option<&'a Ident>,
+    /// The ident of the version parameter, when building the body of `default_for_version`.
+    pub version: Option<&'a Ident>,
+}
+
+/// The default values of a field, for each profile.
+pub struct FieldDefaultValues {
+    ident: Option<Ident>,
+    name: String,
+    ty: Type,
+    values: Profiled<DefaultExpr>,
+    /// The unprofiled values replacing the unqualified one from a version on, from the oldest to the newest.
+    versions: Vec<VersionedValue>,
+    /// Whether the field is built with the `default_with` function of its own type (`#[default_with]`).
+    nested_with_context: bool,
+    /// Whether the default value of the field type is its `auto` default value (`#[default(auto)]`).
+    auto: bool,
+    /// Whether the field is written in place with the `default_in_place` function of its own type (`#[default(in_place)]`).
+    in_place: bool,
+    /// Whether the default values are converted with `Into` (the `into_all` option).
+    into_all: bool,
+    /// Whether an attribute of the field couldn't be parsed, the field then being built with `unreachable!()`
+    /// when it has no default value, so the errors of the attribute aren't followed by errors on the generated code.
+    errored: bool,
+    cfgs: Vec<Attribute>,
+}
+
+impl FieldDefaultValues {
+    /// The unqualified value of the newest version.
+    fn unqualified(&self) -> Option<&DefaultExpr> {
+        self.versions
+            .last()
+            .map(|v| &v.value)
+            .or_else(|| self.values.get(None))
+    }
+
+    /// Whether the field has a default value from an attribute, for any profile or version.
+    pub fn has_values(&self) -> bool {
+        self.values.iter().next().is_some() || !self.versions.is_empty()
+    }
+
+    /// The newest version of the values of this field, `None` without versioned values.
+    pub fn latest_version(&self) -> Option<&LitInt> {
+        self.versions.last().map(|v| &v.since)
+    }
+
+    /// Gets the default value of the field for `selection`,
+    /// falling back to the unqualified value of the newest version, then to the default value of the field type.
+    ///
+    /// With a version parameter, the value of each version is selected at runtime.
+    ///
+    /// The values using the context are skipped when no context is given.
+    pub fn get(&self, selection: Selection) -> DefaultValue {
+        let is_usable = |v: &&DefaultExpr| !v.uses_context() || selection.context.is_some();
+
+        let (value, is_override) = match selection.version.filter(|_| !self.versions.is_empty()) {
+            Some(version) => {
+                let (base, _) = self.tokens(self.values.get(None).filter(is_usable), selection);
+                let versions = self
+                    .versions
+                    .iter()
+                    .filter(|v| is_usable(&&v.value))
+                    .map(|v| (v.version(), v.value.tokens(&self.name, &self.ty, self.into_all)))
+                    .collect();
+
+                (versions::select(version, versions, base), true)
+            }
+            None => {
+                let value = selection
+                    .profile
+                    .and_then(|profile| self.values.get(Some(profile)))
+                    .or_else(|| self.unqualified())
+                    .filter(is_usable);
+
+                self.tokens(value, selection)
+            }
+        };
+
+        DefaultValue {
+            ident: self.ident.clone(),
+            name: self.name.clone(),
+            ty: self.ty.clone(),
+            value,
+            is_override,
+            auto: self.auto,
+            in_place: self.in_place && !is_override,
+            cfgs: self.cfgs.clone(),
+        }
+    }
+
+    /// Gets the tokens of `value`, or of the default value of the field type without one,
+    /// and whether they come from an attribute.
+    fn tokens(&self, value: Option<&DefaultExpr>, selection: Selection) -> (TokenStream2, bool) {
+        let ty = &self.ty;
+        match (value, selection.context) {
+            (Some(value), _) => (value.tokens(&self.name, ty, self.into_all), true),
+            (None, Some(context)) if self.nested_with_context => {
+                (quote! { <#ty>::default_with(#context) }, false)
+            }
+            // the closure gives the value the field type, rather than making the next fields unreachable
+            (None, _) if self.errored => (
+                quote_spanned! { ty.span()=> (|| -> #ty { unreachable!() })() },
+                true,
+            ),
+            _ => (types::default_value(ty, self.auto), false),
+        }
+    }
+}
+
+impl FieldDefaultValues {
+    /// Marks the field as errored, after an error in an attribute which could have given it a default value.
+    pub fn mark_errored(&mut self) {
+        self.errored = true;
+    }
+
+    /// Reports the default values of this field which call the `Default` implementation of the type `ident`,
+    /// or the missing default value of a field whose type holds the type `ident`.
+    ///
+    /// Only the values used by `Default::default` and `default_for_version` are checked.
+    pub fn check_recursion(&self, ident: &Ident, error_tokens: &mut Vec<TokenStream2>) {
+        let base = self.values.get(None).filter(|v| !v.uses_context());
+        // without a value of the version 0, the field is built with the `Default` implementation of its type
+        if base.is_none() && recursion::is_recursive_type(&self.ty, ident) {
+            let span = self.ident.as_ref().map_or_else(|| self.ty.span(), Ident::span);
+            recursion::check_field(span, ident).emit(error_tokens);
+        }
+
+        let versions = self.versions.iter().map(|v| &v.value).filter(|v| !v.uses_context());
+        for value in base.into_iter().chain(versions).flat_map(DefaultExpr::branches) {
+            let DefaultExpr { value: Value::Expr(expr), modifiers, .. } = value else {
+                continue;
+            };
+
+            let ty = types::wrapped_type(&self.ty, modifiers);
+            for diagnostic in recursion::check_value(expr, ty, ident) {
+                diagnostic.emit(error_tokens);
+            }
+        }
+    }
+}
+
+impl FieldDefaultValues {
+    /// Warns about the default values which change nothing, unless their lint is allowed :
+    /// - `redundant` : the value is the default value of the field type, which the field would have anyway.
+    /// - `redundant_profile` : the value of a profile is the same as the unqualified value, which the profile falls back to.
+    ///
+    /// The versioned values aren't checked, since they replace the values of the older versions.
+    pub fn lint(&self, options: &ContainerOptions, warnings: &mut Vec<TokenStream2>) {
+        let unqualified = self.unqualified();
+
+        for (profile, value) in self.values.iter() {
+            let Value::Expr(expr) = &value.value else {
+                continue;
+            };
+            // the values of the other configurations make the value meaningful, even if it's redundant on its own
+            if value.each.is_some() || !value.modifiers.is_empty() || !value.alternatives.is_empty() {
+                continue;
+            }
+
+            match (profile, unqualified) {
+                (Some(profile), Some(unqualified)) => {
+                    let is_same = matches!(&unqualified.value, Value::Expr(u) if lints::is_same_value(u, expr))
+                        && unqualified.each.is_none()
+                        && unqualified.modifiers.is_empty()
+                        && unqualified.alternatives.is_empty()
+                        && unqualified.context == value.context;
+
+                    if is_same && !options.allows(constants::REDUNDANT_PROFILE_LINT) {
+                        let message = format!(
+                            "the value of the profile `{}` is the same as the unqualified default value, which the profile already falls back to",
+                            profile.value()
+                        );
+                        warnings.push(lints::warning(
+                            constants::REDUNDANT_PROFILE_LINT,
+                            expr.span(),
+                            &message,
+                        ));
+                    }
+                }
+                _ if options.allows(constants::REDUNDANT_LINT) => (),
+                _ if lints::is_redundant(expr, &self.ty) => {
+                    let message = format!(
+                        "this value is already the default value of `{}`",
+                        types::display(&self.ty)
+                    );
+                    warnings.push(lints::warning(constants::REDUNDANT_LINT, expr.span(), &message));
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Finds the `#[default_with]` attribute marking a field built with the context.
+fn find_nested_with_context<'a>(
+    field: &'a Field,
+    error_tokens: &mut Vec<TokenStream2>,
+) -> Option<&'a Attribute> {
+    let attr = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(constants::DEFAULT_WITH_IDENT))?;
+
+    handle_error!(attr.meta.require_path_only(), error_tokens).map(|_| attr)
+}
+
+pub fn get_field_default_values(
+    top_default_values: &Profiled<HashMap<String, DefaultExpr>>,
     fields: &Fields,
+    context: Option<&Ident>,
+    options: &ContainerOptions,
     error_tokens: &mut Vec<TokenStream2>,
-) -> Vec<DefaultValue> {
+) -> Vec<FieldDefaultValues> {
     let mut default_values_vec = Vec::with_capacity(fields.len());
     for (i, field) in fields.iter().enumerate() {
         let ident = field.ident.clone();
@@ -35,57 +742,173 @@ fn get_field_default_values(
 
         let ty = &field.ty;
 
-        let default_tokens = attrs::find_attribute_unique(
-            &field.attrs,
+        let mut values = Profiled::default();
+        let mut errored = false;
+        let mut field_auto = false;
+        let mut in_place = None;
+        // the versioned attributes come beside the unqualified one
+        let (versioned, attributes): (Vec<_>, Vec<_>) =
+            field.attrs.iter().cloned().partition(versions::is_versioned);
+        for (profile, attr) in attrs::find_attribute_unique_per_profile(
+            &attributes,
             constants::DEFAULT_IDENT,
             error_tokens,
-        )
-            .and_then(|attr| handle_error!(attr.meta.require_list(), error_tokens));
-
-        let top_default_tokens = top_default_values
-            .and_then(|h| h.get(&ident_str))
-            .map(ToTokens::to_token_stream);
+        ) {
+            let Some(meta) = handle_error!(attr.meta.require_list(), error_tokens) else {
+                errored = true;
+                continue;
+            };
 
-        if let Some(meta) = default_tokens {
-            if top_default_tokens.is_some() {
+            if top_default_values
+                .get(profile.as_ref())
+                .is_some_and(|h| h.contains_key(&ident_str))
+            {
                 error!(
                     error_tokens,
                     meta.path.span(),
                     "a default value for this field already exists in the top default attribute."
                 );
             }
+
+            let Some(attr) = handle_error!(meta.parse_args::<FieldAttribute>(), error_tokens) else {
+                errored = true;
+                continue;
+            };
+
+            match (attr.value, attr.marker) {
+                (Some(value), _) => {
+                    value.check(ty, context, error_tokens);
+                    values.insert(profile, value);
+                }
+                (None, Some(marker)) if marker == constants::IN_PLACE_IDENT => {
+                    in_place = Some(marker)
+                }
+                _ => field_auto = true,
+            }
         }
 
-        let default_tokens = default_tokens.and_then(|meta| handle_error!(meta.parse_args::<Expr>(), error_tokens));
+        let mut versions = Vec::new();
+        for attr in &versioned {
+            let attr = attr.meta.require_list().and_then(|meta| meta.parse_args::<FieldAttribute>());
+            let Some(FieldAttribute {
+                value: Some(value),
+                since: Some(since),
+                ..
+            }) = handle_error!(attr, error_tokens)
+            else {
+                errored = true;
+                continue;
+            };
 
-        let default_tokens = default_tokens
-            .map(ToTokens::into_token_stream)
-            .or(top_default_tokens)
-            .unwrap_or(quote! { <#ty as #DefaultTraitPath>::default() });
+            value.check(ty, context, error_tokens);
+            versions::push(&mut versions, VersionedValue { since, value }, error_tokens);
+        }
+
+        for (profile, top_values) in top_default_values.iter() {
+            if values.get(profile).is_some() {
+                continue;
+            }
+
+            if let Some(value) = top_values.get(&ident_str) {
+                value.check(ty, context, error_tokens);
+                values.insert(profile.cloned(), value.clone());
+            }
+        }
+
+        let nested_with_context = find_nested_with_context(field, error_tokens);
+        let has_value = values.get(None).is_some() || !versions.is_empty();
+        if let Some(attr) = nested_with_context.filter(|_| has_value) {
+            error!(
+                error_tokens,
+                attr.meta.span(),
+                "a field with a default value can't also be marked with `#[{}]`.",
+                constants::DEFAULT_WITH_IDENT
+            );
+        }
+
+        if let Some(marker) = in_place.as_ref().filter(|_| options.in_place.is_none()) {
+            error!(
+                error_tokens,
+                marker.span(),
+                "`{}` on a field requires the `{}` option in the top attribute.",
+                constants::IN_PLACE_IDENT,
+                constants::IN_PLACE_OPTION
+            );
+        }
+
+        let auto = field_auto || options.auto.is_some();
+        if auto && values.get(None).is_none() && nested_with_context.is_none() && !errored {
+            types::check_auto(ty, error_tokens);
+        }
 
-        let default_value = DefaultValue {
+        let default_values = FieldDefaultValues {
             ident,
-            value: default_tokens,
+            name: ident_str,
+            ty: ty.clone(),
+            values,
+            versions,
+            nested_with_context: nested_with_context.is_some(),
+            auto,
+            in_place: in_place.is_some(),
+            into_all: options.into_all.is_some(),
+            errored,
+            cfgs: attrs::cfg_attributes(&field.attrs),
         };
-        default_values_vec.push(default_value);
+        default_values_vec.push(default_values);
     }
 
     default_values_vec
 }
 
-pub fn derive_body(
-    top_default_values: Option<&HashMap<String, Expr>>,
-    fields: &Fields,
-    error_tokens: &mut Vec<TokenStream2>,
-) -> TokenStream2 {
+/// Collects the profiles used by the top default attributes and the fields, in declaration order.
+pub fn get_profiles(
+    top_default_values: &Profiled<HashMap<String, DefaultExpr>>,
+    default_values: &[FieldDefaultValues],
+) -> Vec<LitStr> {
+    let mut profiles = Vec::new();
+
+    let field_profiles = default_values.iter().flat_map(|v| v.values.profiles());
+    for p in top_default_values.profiles().chain(field_profiles) {
+        profile::register(&mut profiles, p);
+    }
+
+    profiles
+}
+
+pub fn derive_body(default_values: Vec<DefaultValue>, fields: &Fields) -> TokenStream2 {
     let delimiter = match fields {
         Fields::Named(_) => proc_macro2::Delimiter::Brace,
         Fields::Unnamed(_) => proc_macro2::Delimiter::Parenthesis,
         Fields::Unit => return TokenStream2::new(),
     };
 
-    let default_value_vec = get_field_default_values(top_default_values, fields, error_tokens);
+    // the attributes can't be put on the arguments of a call, so the unnamed fields with a `cfg` use their index
+    let by_index = delimiter == proc_macro2::Delimiter::Parenthesis
+        && default_values.iter().any(|value| !value.cfgs.is_empty());
+    let delimiter = if by_index {
+        proc_macro2::Delimiter::Brace
+    } else {
+        delimiter
+    };
+
+    // the separators carry the span of their field, so that no token of the body points at the derive
+    let mut tokens = TokenStream2::new();
+    for (value, field) in default_values.iter().zip(fields) {
+        if by_index {
+            let cfgs = &value.cfgs;
+            let member = value.member();
+            let field_value = &value.value;
+            quote! { #(#cfgs)* #member: #field_value }.to_tokens(&mut tokens);
+        } else {
+            value.to_tokens(&mut tokens);
+        }
+        Token![,](field.span()).to_tokens(&mut tokens);
+    }
 
-    let flattened_tokens = default_value_vec.join_tokens(&Token![,](Span2::call_site()));
-    proc_macro2::Group::new(delimiter, flattened_tokens).into_token_stream()
+    let mut group = proc_macro2::Group::new(delimiter, tokens);
+    // the indices written by the derive keep its span, rather than looking written by the user
+    if !by_index {
+        group.set_span(fields.span());
+    }
+    group.into_token_stream()
 }
@@ -0,0 +1,83 @@
+use quote::quote;
+use syn::{parse::Parse, DeriveInput, Ident, Token, Type};
+
+use crate::{attrs, constants, default::Selection, derive, Span2, TokenStream2};
+
+/// The context parameter declared by the `#[default_with(<ident>: <type>)]` attribute.
+pub struct Context {
+    pub ident: Ident,
+    _colon: Token![:],
+    ty: Type,
+}
+
+impl Parse for Context {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            ident: input.parse()?,
+            _colon: input.parse()?,
+            ty: input.parse()?,
+        })
+    }
+}
+
+pub fn get_context(input: &DeriveInput, error_tokens: &mut Vec<TokenStream2>) -> Option<Context> {
+    let attr = attrs::find_attribute_unique(
+        &input.attrs,
+        constants::DEFAULT_WITH_IDENT,
+        error_tokens,
+    )?;
+
+    handle_error!(attr.parse_args::<Context>(), error_tokens)
+}
+
+pub fn derive(input: &DeriveInput) -> TokenStream2 {
+    if let Some(error_tokens) = derive::reject_union(input) {
+        return error_tokens;
+    }
+
+    let mut error_tokens = Vec::new();
+
+    let context = get_context(input, &mut error_tokens);
+    let Some(context) = context else {
+        if error_tokens.is_empty() {
+            error!(
+                error_tokens,
+                Span2::call_site(),
+                "this derive requires a `#[{}(<ident>: <type>)]` attribute declaring the context.",
+                constants::DEFAULT_WITH_IDENT
+            );
+        }
+
+        return error_tokens.into_iter().collect();
+    };
+
+    let default_fields = derive::get_default_fields(input, Some(&context.ident), &mut error_tokens);
+    if let Some(default_fields) = &default_fields {
+        default_fields.reject_conditional("DefaultWith", &mut error_tokens);
+    }
+    let selection = Selection {
+        context: Some(&context.ident),
+        ..Default::default()
+    };
+    let body = default_fields.map_or_else(|| quote! { panic!() }, |f| f.body(selection));
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let error_tokens: TokenStream2 = error_tokens.into_iter().collect();
+
+    let context_ident = &context.ident;
+    let context_ty = &context.ty;
+
+    quote! {
+        impl #impl_generics #ident #type_generics #where_clause {
+            /// Returns the default value, built with the given context.
+            #[allow(unused_variables)]
+            #vis fn default_with(#context_ident: #context_ty) -> Self {
+                #body
+            }
+        }
+
+        #error_tokens
+    }
+}
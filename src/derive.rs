@@ -1,35 +1,282 @@
-use quote::quote;
+use std::collections::HashMap;
+
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    spanned::Spanned, Attribute, DataEnum, DataStruct, DeriveInput,
-    Fields,
+    spanned::Spanned, Attribute, DataEnum, DeriveInput,
+    Fields, Ident, LitInt, LitStr, Variant,
 };
 
 use crate::{
-    attrs, default,
-    top_attribute,
+    attrs, cached, condition::{self, Branch, Condition, ConditionalDefault}, default::{self, DefaultExpr, DefaultValue, FieldDefaultValues, Selection},
+    default_with, diagnostic::Diagnostic, in_place,
+    options::ContainerOptions,
+    profile::{self, Profiled},
+    top_attribute::{self, Location},
+    trace_panics, try_default, validate, variants, versions, zeroed,
     Span2, TokenStream2, constants::{self, DefaultTraitPath}
 };
 
-fn search_and_mark_default_attribute_on_fields(
-    fields: &Fields,
-    error_tokens: &mut Vec<TokenStream2>,
-) {
-    for field in fields {
-        if let Some(attribute) = attrs::find_attribute_unique(
-            &field.attrs,
-            constants::DEFAULT_IDENT,
-            error_tokens,
-        ) {
+type ProfiledAttributes<'l> = [(Option<LitStr>, &'l Attribute)];
+
+/// The default values of the struct or of the default variant.
+pub struct DefaultFields<'a> {
+    /// The path used to build the value : `Self` or `Self::<variant>`.
+    path: TokenStream2,
+    /// The name of the path, used in messages : `<type>` or `<type>::<variant>`.
+    pub name: String,
+    fields: &'a Fields,
+    values: Vec<FieldDefaultValues>,
+    pub profiles: Vec<LitStr>,
+    pub options: ContainerOptions,
+    /// The default values of the variants of an enum getting a constructor, in declaration order, empty for a struct.
+    pub variants: Vec<VariantDefaults<'a>>,
+    /// The default variants of an enum selected by a condition, in declaration order.
+    pub conditional: Vec<ConditionalDefault<'a>>,
+    /// Whether these default values are unconditional, rather than standing for the first conditional default variant
+    /// of an enum without an unconditional one.
+    pub is_fallback: bool,
+    /// Whether the type declares a context, for which `DefaultWith` generates `default_with`.
+    has_context: bool,
+}
+
+/// The default values of a variant, used by its `default_<variant>` constructor.
+pub struct VariantDefaults<'a> {
+    pub ident: Ident,
+    /// The default values of the variant, `None` for the default variant, which is built like `Default`.
+    pub fields: Option<DefaultFields<'a>>,
+}
+
+impl DefaultFields<'_> {
+    pub fn values(&self, selection: Selection) -> Vec<DefaultValue> {
+        self.values.iter().map(|v| v.get(selection)).collect()
+    }
+
+    /// Builds the value from the default values of its fields.
+    ///
+    /// The value is then checked with the `validate` option, if it's used.
+    pub fn build(&self, values: Vec<DefaultValue>) -> TokenStream2 {
+        let tokens = self.build_unchecked(values);
+
+        match &self.options.validate {
+            Some(validate) => validate::validate_body(validate, tokens, &self.name),
+            None => tokens,
+        }
+    }
+
+    fn build_unchecked(&self, values: Vec<DefaultValue>) -> TokenStream2 {
+        let path = &self.path;
+
+        if self.options.zeroed.is_some() {
+            return zeroed::build(values);
+        }
+
+        // without the std library, the panics can't be reported
+        if cfg!(feature = "std") && self.options.trace_panics.is_some() {
+            return trace_panics::build(path, &self.name, values, self.fields);
+        }
+
+        let body = default::derive_body(values, self.fields);
+
+        quote! { #path #body }
+    }
+
+    /// Reports the default values recursing into the `Default` implementation of the type `ident`,
+    /// unless the `recursion` lint is allowed.
+    pub fn check_recursion(&self, ident: &Ident, error_tokens: &mut Vec<TokenStream2>) {
+        if self.options.allows(constants::RECURSION_LINT) {
+            return;
+        }
+
+        for value in &self.values {
+            value.check_recursion(ident, error_tokens);
+        }
+
+        for conditional in &self.conditional {
+            conditional.fields.check_recursion(ident, error_tokens);
+        }
+    }
+
+    fn variant_with_constructor(&self, fn_ident: &Ident) -> Option<&VariantDefaults<'_>> {
+        self.variants
+            .iter()
+            .find(|v| variants::constructor_ident(&v.ident) == *fn_ident)
+    }
+
+    /// The functions generated by the options and the versioned values, with what generates them,
+    /// whose names can't be used by the profile and variant constructors.
+    fn option_functions(&self) -> Vec<(&'static str, String)> {
+        let mut functions = Vec::new();
+        if let Some(in_place) = &self.options.in_place {
+            for name in ["default_in_place", "default_boxed"] {
+                functions.push((name, format!("the `{}` option", in_place)));
+            }
+        }
+        if let Some(cached) = &self.options.cached {
+            functions.push(("default_ref", format!("the `{}` option", cached)));
+        }
+        if self.latest_version().is_some() && self.conditional.is_empty() {
+            functions.push(("default_for_version", "the versioned default values".to_string()));
+        }
+        if self.has_context {
+            functions.push(("default_with", "the `DefaultWith` derive".to_string()));
+        }
+
+        functions
+    }
+
+    /// Whether the constructor of a profile has the name of another generated function.
+    fn is_conflicting(&self, fn_ident: &Ident) -> bool {
+        self.variant_with_constructor(fn_ident).is_some()
+            || self.option_functions().iter().any(|(name, _)| fn_ident == name)
+    }
+
+    /// Whether the constructor of a variant has the name of a function generated by an option.
+    fn has_conflicting_variant(&self) -> bool {
+        let option_functions = self.option_functions();
+
+        self.variants.iter().any(|variant| {
+            let fn_ident = variants::constructor_ident(&variant.ident);
+            option_functions.iter().any(|(name, _)| fn_ident == name)
+        })
+    }
+
+    /// Reports the profiles whose constructor has the name of a variant constructor,
+    /// and the profiles and variants whose constructor has the name of a function generated by an option.
+    pub fn check_constructors(&self, error_tokens: &mut Vec<TokenStream2>) {
+        let option_functions = self.option_functions();
+
+        for variant in &self.variants {
+            let fn_ident = variants::constructor_ident(&variant.ident);
+            if let Some((_, generator)) = option_functions.iter().find(|(name, _)| fn_ident == name) {
+                Diagnostic::new(
+                    variant.ident.span(),
+                    format!(
+                        "the constructor `{}` of this variant is also generated by {}.",
+                        fn_ident, generator
+                    ),
+                )
+                .help("rename the variant.")
+                .emit(error_tokens);
+            }
+        }
+
+        for profile in &self.profiles {
+            let fn_ident = profile::constructor_ident(profile);
+            if let Some((_, generator)) = option_functions.iter().find(|(name, _)| fn_ident == name) {
+                Diagnostic::new(
+                    profile.span(),
+                    format!(
+                        "the constructor `{}` of this profile is also generated by {}.",
+                        fn_ident, generator
+                    ),
+                )
+                .help("rename the profile.")
+                .emit(error_tokens);
+            }
+
+            if let Some(variant) = self.variant_with_constructor(&fn_ident) {
+                Diagnostic::new(
+                    profile.span(),
+                    format!(
+                        "the constructor `{}` of this profile is also the constructor of the variant `{}`.",
+                        fn_ident, variant.ident
+                    ),
+                )
+                .help("rename the profile.")
+                .span_note(variant.ident.span(), format!("the variant `{}` is declared", variant.ident))
+                .emit(error_tokens);
+            }
+        }
+    }
+
+    /// Warns about the default values which change nothing.
+    pub fn lint(&self, warnings: &mut Vec<TokenStream2>) {
+        for value in &self.values {
+            value.lint(&self.options, warnings);
+        }
+
+        for fields in self.variants.iter().filter_map(|v| v.fields.as_ref()) {
+            fields.lint(warnings);
+        }
+
+        for conditional in &self.conditional {
+            conditional.fields.lint(warnings);
+        }
+    }
+
+    /// The body of the `Default` implementation when no condition holds, `None` if it can't be reached
+    /// without an unconditional default variant, since the `cfg` conditions may not cover every configuration.
+    fn fallback_body(&self) -> Option<TokenStream2> {
+        let has_cfg = self
+            .conditional
+            .iter()
+            .any(|c| matches!(c.condition, Condition::Cfg(_)));
+
+        (self.is_fallback || !has_cfg).then(|| self.body(Selection::default()))
+    }
+
+    /// Reports the conditional default variants, which only the `Default` derive supports.
+    pub fn reject_conditional(&self, derive: &str, error_tokens: &mut Vec<TokenStream2>) {
+        for conditional in &self.conditional {
             error!(
                 error_tokens,
-                attribute.meta.span(),
-                "You can't use the default attribute on variant fields if the variant is not declared as default."
+                conditional.condition.span(),
+                "the conditional default variants aren't supported by the `{}` derive.",
+                derive
             );
         }
     }
+
+    /// Whether a field has a default value from an attribute.
+    fn has_values(&self) -> bool {
+        self.values.iter().any(FieldDefaultValues::has_values)
+    }
+
+    pub fn body(&self, selection: Selection) -> TokenStream2 {
+        self.build(self.values(selection))
+    }
+
+    /// The newest version of the default values of the fields, `None` without versioned values.
+    pub fn latest_version(&self) -> Option<&LitInt> {
+        self.values
+            .iter()
+            .filter_map(FieldDefaultValues::latest_version)
+            .max_by_key(|since| since.base10_parse::<u32>().unwrap())
+    }
+
+    /// Generates `DEFAULTS_VERSION` and `default_for_version`, if some fields have versioned values.
+    fn version_functions(&self, vis: &syn::Visibility) -> Option<TokenStream2> {
+        // with conditions, the versioned values are rejected
+        let latest = self.latest_version().filter(|_| self.conditional.is_empty())?;
+
+        let version = versions::parameter();
+        let body = self.body(Selection {
+            version: Some(&version),
+            ..Default::default()
+        });
+
+        Some(versions::functions(vis, latest, body))
+    }
+
+    /// Generates `default_in_place` and `default_boxed`, if the `in_place` option is used.
+    pub fn in_place_functions(&self, vis: &syn::Visibility) -> Option<TokenStream2> {
+        self.options.in_place.as_ref()?;
+
+        let slot = format_ident!("slot", span = Span2::mixed_site());
+        let mut body = in_place::build(
+            &slot,
+            self.values(Selection::default()),
+            self.options.zeroed.is_some(),
+        );
+        if let Some(validate) = &self.options.validate {
+            body.extend(validate::validate_tokens(validate, quote! { &*#slot }, &self.name));
+        }
+
+        Some(in_place::functions(vis, body, &slot))
+    }
 }
 
-fn get_fields_name(fields: &Fields) -> Vec<String> {
+pub fn get_fields_name(fields: &Fields) -> Vec<String> {
     match fields {
         Fields::Named(named) => named
             .named
@@ -41,114 +288,588 @@ fn get_fields_name(fields: &Fields) -> Vec<String> {
     }
 }
 
-fn derive_struct(
-    top_attribute: Option<&Attribute>,
-    data: &DataStruct,
+/// The content of the top default attributes.
+#[derive(Default)]
+struct TopDefaultValues {
+    values: Profiled<HashMap<String, DefaultExpr>>,
+    options: ContainerOptions,
+    /// The condition of the unqualified attribute of a variant, selecting it as the default one.
+    condition: Option<Condition>,
+    /// Whether a top attribute couldn't be parsed, losing default values and options.
+    errored: bool,
+}
+
+/// Parses the top default attributes, returning the default values of each profile and the options.
+fn get_top_default_values(
+    top_attributes: &ProfiledAttributes,
+    fields: &Fields,
+    location: Location,
+    error_tokens: &mut Vec<TokenStream2>,
+) -> TopDefaultValues {
+    let mut top_default_values = TopDefaultValues::default();
+
+    for (profile, attr) in top_attributes {
+        let Some(top_attribute) = top_attribute::get_default_values(
+            attr,
+            fields,
+            location,
+            profile.is_some(),
+            error_tokens,
+        ) else {
+            top_default_values.errored = true;
+            continue;
+        };
+
+        if profile.is_none() {
+            top_default_values.options = top_attribute.options;
+            top_default_values.condition = top_attribute.condition;
+        }
+
+        top_default_values
+            .values
+            .insert(profile.clone(), top_attribute.values);
+    }
+
+    top_default_values
+}
+
+fn get_fields_default_values<'a>(
+    path: TokenStream2,
+    name: String,
+    top_default_values: TopDefaultValues,
+    fields: &'a Fields,
+    context: Option<&Ident>,
+    error_tokens: &mut Vec<TokenStream2>,
+) -> DefaultFields<'a> {
+    let TopDefaultValues {
+        values: top_default_values,
+        options,
+        errored,
+        ..
+    } = top_default_values;
+
+    let mut values = default::get_field_default_values(
+        &top_default_values,
+        fields,
+        context,
+        &options,
+        error_tokens,
+    );
+    let profiles = default::get_profiles(&top_default_values, &values);
+
+    // the fields could have had a default value in the invalid attribute
+    if errored {
+        values.iter_mut().for_each(FieldDefaultValues::mark_errored);
+    }
+
+    if options.zeroed.is_some() {
+        for field in fields {
+            handle_error!(zeroed::check_zero_valid(&field.ty), error_tokens);
+        }
+    }
+
+    DefaultFields {
+        path,
+        name,
+        fields,
+        values,
+        profiles,
+        options,
+        variants: Vec::new(),
+        conditional: Vec::new(),
+        is_fallback: true,
+        has_context: context.is_some(),
+    }
+}
+
+/// Gets the default values of an enum variant, with the options of the enum.
+fn get_variant_default_values<'a>(
+    enum_ident: &Ident,
+    variant: &'a Variant,
+    mut top_default_values: TopDefaultValues,
+    options: &ContainerOptions,
+    context: Option<&Ident>,
     error_tokens: &mut Vec<TokenStream2>,
-) -> TokenStream2 {
-    let field_names = get_fields_name(&data.fields);
-    let top_attribute =
-        top_attribute.and_then(|attr| top_attribute::get_default_values(attr, &field_names, true, error_tokens));
+) -> DefaultFields<'a> {
+    let ident = &variant.ident;
+    top_default_values.options = options.clone();
+
+    get_fields_default_values(
+        quote_spanned! { ident.span()=> Self::#ident },
+        format!("{}::{}", enum_ident, ident),
+        top_default_values,
+        &variant.fields,
+        context,
+        error_tokens,
+    )
+}
 
-    let body_tokens = default::derive_body(top_attribute.as_ref(), &data.fields, error_tokens);
+/// Gets the default values of a default variant again, its errors being already reported.
+fn reparse_default_variant<'a>(
+    enum_ident: &Ident,
+    variant: &'a Variant,
+    options: &ContainerOptions,
+    context: Option<&Ident>,
+) -> DefaultFields<'a> {
+    let error_tokens = &mut Vec::new();
+    let attributes = attrs::find_attribute_unique_per_profile(
+        &variant.attrs,
+        constants::DEFAULT_IDENT,
+        error_tokens,
+    );
+    let top_default_values =
+        get_top_default_values(&attributes, &variant.fields, Location::Variant, error_tokens);
 
-    quote! { Self #body_tokens }
+    get_variant_default_values(enum_ident, variant, top_default_values, options, context, error_tokens)
 }
 
-fn derive_enum(
-    top_attribute: Option<&Attribute>,
-    data: &DataEnum,
+fn get_enum_default_fields<'a>(
+    enum_ident: &Ident,
+    top_attributes: &ProfiledAttributes,
+    data: &'a DataEnum,
+    context: Option<&Ident>,
     error_tokens: &mut Vec<TokenStream2>,
-) -> TokenStream2 {
-    if let Some(attr) = top_attribute {
+) -> Option<DefaultFields<'a>> {
+    let enum_top_default_values =
+        get_top_default_values(top_attributes, &Fields::Unit, Location::Enum, error_tokens);
+    let mut options = enum_top_default_values.options;
+    let errored = enum_top_default_values.errored;
+
+    for ident in [options.zeroed.take(), options.in_place.take()]
+        .into_iter()
+        .flatten()
+    {
         error!(
             error_tokens,
-            attr.meta.span(),
-            "top default attributes are not allowed on enums."
+            ident.span(),
+            "`{}` can only be used on structs.",
+            ident
         );
     }
 
-    let mut default_variant = None;
+    let mut default_variant: Option<(&Variant, &Attribute, _)> = None;
+    let mut conditional = Vec::new();
     for variant in &data.variants {
-        let Some(attr) = attrs::find_attribute_unique(
+        let attributes = attrs::find_attribute_unique_per_profile(
             &variant.attrs,
             constants::DEFAULT_IDENT,
             error_tokens,
-        ) else {
-            search_and_mark_default_attribute_on_fields(&variant.fields, error_tokens);
+        );
+
+        let Some((_, attr)) = attributes.iter().find(|(profile, _)| profile.is_none()) else {
+            for (_, attr) in &attributes {
+                error!(
+                    error_tokens,
+                    attr.meta.span(),
+                    "You can't use a profiled default attribute on a variant if the variant is not declared as default."
+                );
+            }
 
             continue;
         };
 
-        if let Some((ident, _)) = default_variant.as_ref() {
-            error!(
+        let mut top_default_values =
+            get_top_default_values(&attributes, &variant.fields, Location::Variant, error_tokens);
+        top_default_values.errored |= errored;
+
+        // a conditional attribute which couldn't be parsed isn't a second unconditional default variant
+        if top_default_values.condition.is_none() && condition::has_condition(attr) {
+            continue;
+        }
+
+        if let Some(condition) = top_default_values.condition.take() {
+            let fields = get_variant_default_values(
+                enum_ident,
+                variant,
+                top_default_values,
+                &options,
+                context,
                 error_tokens,
-                attr.meta.span(),
-                "the default value is already assigned to `{}`",
-                ident
             );
 
+            for profile in &fields.profiles {
+                error!(
+                    error_tokens,
+                    profile.span(),
+                    "the profiles can only be used in the unconditional default variant."
+                );
+            }
+            reject_versions(&fields, "the unconditional default variant", error_tokens);
+
+            conditional.push(ConditionalDefault {
+                ident: variant.ident.clone(),
+                condition,
+                fields,
+            });
+
+            continue;
+        }
+
+        if let Some((first, first_attr, _)) = default_variant.as_ref() {
+            Diagnostic::new(
+                attr.meta.span(),
+                format_args!("the default value is already assigned to `{}`", first.ident),
+            )
+            .span_note(
+                first_attr.meta.span(),
+                format_args!("`{}` is declared as default", first.ident),
+            )
+            .help(format_args!(
+                "to select a default variant per configuration, use `#[{}(cfg(<predicate>))]`.",
+                constants::DEFAULT_IDENT
+            ))
+            .emit(error_tokens);
+
             continue;
         }
 
-        let field_names = get_fields_name(&variant.fields);
-        let top_attribute = top_attribute::get_default_values(attr, &field_names, false, error_tokens);
+        default_variant = Some((variant, *attr, top_default_values));
+    }
+
+    // without a default variant, the other variants are built like errored ones, so their constructors don't add errors
+    let variants_errored = default_variant.is_none() && conditional.is_empty();
+    let is_fallback = default_variant.is_some();
+    // the ident of the unconditional default variant
+    let (mut default_fields, default_ident) = match default_variant {
+        Some((variant, _, top_default_values)) => (
+            get_variant_default_values(
+                enum_ident,
+                variant,
+                top_default_values,
+                &options,
+                context,
+                error_tokens,
+            ),
+            Some(&variant.ident),
+        ),
+        None if !conditional.is_empty() => {
+            if conditional
+                .iter()
+                .all(|c| matches!(c.condition, Condition::When(_)))
+            {
+                error!(
+                    error_tokens,
+                    Span2::call_site(),
+                    "the `{}` conditions need an unconditional default variant to fall back to.",
+                    constants::WHEN_IDENT
+                );
+            }
 
-        let headless_default_tokens =
-            default::derive_body(top_attribute.as_ref(), &variant.fields, error_tokens);
-        let ident = variant.ident.clone();
-        // FIXME: for some reason the "value holding a reference to a value owned by the current function"
-        //  error has the Span::call_site() span, and idk why.
-        let default_tokens: TokenStream2 = quote! { Self::#ident #headless_default_tokens };
+            // the first conditional variant stands for the default variant, for the options and the profiles
+            let variant = data
+                .variants
+                .iter()
+                .find(|v| v.ident == conditional[0].ident)
+                .unwrap();
+            (reparse_default_variant(enum_ident, variant, &options, context), None)
+        }
+        None => {
+            error!(
+                error_tokens,
+                Span2::call_site(),
+                "the default variant has not been set."
+            );
 
-        default_variant = Some((ident, default_tokens));
+            // the first unit variant keeps the implementation usable beside the error
+            let variant = data.variants.iter().find(|v| v.fields.is_empty())?;
+            let top_default_values = TopDefaultValues {
+                errored,
+                ..Default::default()
+            };
+            (
+                get_variant_default_values(
+                    enum_ident,
+                    variant,
+                    top_default_values,
+                    &options,
+                    context,
+                    error_tokens,
+                ),
+                Some(&variant.ident),
+            )
+        }
+    };
+
+    // the other variants only have their field attributes, with the options of the enum
+    default_fields.variants = data
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let is_default = default_ident == Some(&variant.ident);
+            // with conditions, `Default` doesn't always build the unconditional default variant
+            let fields = (!is_default || !conditional.is_empty()).then(|| {
+                if is_default || conditional.iter().any(|c| c.ident == variant.ident) {
+                    return reparse_default_variant(enum_ident, variant, &options, context);
+                }
+
+                let top_default_values = TopDefaultValues {
+                    errored: errored || variants_errored,
+                    ..Default::default()
+                };
+                let fields = get_variant_default_values(
+                    enum_ident,
+                    variant,
+                    top_default_values,
+                    &options,
+                    context,
+                    error_tokens,
+                );
+
+                for profile in &fields.profiles {
+                    error!(
+                        error_tokens,
+                        profile.span(),
+                        "the profiles can only be used in the default variant."
+                    );
+                }
+                reject_versions(&fields, "the default variant", error_tokens);
+
+                fields
+            });
+
+            // without the `variants` option, only the other variants with default values get a constructor,
+            // so the variants holding types without a `Default` implementation are left as they are
+            let is_selectable = is_default || conditional.iter().any(|c| c.ident == variant.ident);
+            let has_constructor = options.variants.is_some()
+                || (!is_selectable && fields.as_ref().is_some_and(DefaultFields::has_values));
+
+            has_constructor.then(|| VariantDefaults {
+                ident: variant.ident.clone(),
+                fields,
+            })
+        })
+        .collect();
+    if is_fallback && !conditional.is_empty() {
+        reject_versions(&default_fields, "an enum without conditional default variants", error_tokens);
     }
+    default_fields.conditional = conditional;
+    default_fields.is_fallback = is_fallback;
 
-    if let Some((_, tokens)) = default_variant {
-        tokens
-    } else {
+    Some(default_fields)
+}
+
+/// Reports the versioned values of a variant, which can only be used in `location`.
+fn reject_versions(fields: &DefaultFields, location: &str, error_tokens: &mut Vec<TokenStream2>) {
+    if let Some(since) = fields.latest_version() {
         error!(
             error_tokens,
-            Span2::call_site(),
-            "the default variant has not been set."
+            since.span(),
+            "the versioned default values can only be used in {}.",
+            location
         );
+    }
+}
+
+/// Returns the error of the derives on unions, which aren't supported.
+pub fn reject_union(input: &DeriveInput) -> Option<TokenStream2> {
+    match &input.data {
+        syn::Data::Union(data) => Some(
+            error!(
+                data.union_token.span(),
+                "this derive is not implemented for unions."
+            )
+            .into_compile_error(),
+        ),
+        _ => None,
+    }
+}
+
+/// Gets the default values of the struct or of the default variant.
+///
+/// When the default variant is missing, the first unit variant is used beside the error, `None` if there's none.
+///
+/// The unions must be rejected beforehand with [`reject_union`].
+pub fn get_default_fields<'a>(
+    input: &'a DeriveInput,
+    context: Option<&Ident>,
+    error_tokens: &mut Vec<TokenStream2>,
+) -> Option<DefaultFields<'a>> {
+    let top_attributes = attrs::find_attribute_unique_per_profile(
+        &input.attrs,
+        constants::DEFAULT_IDENT,
+        error_tokens,
+    );
 
-        quote! { panic!() }
+    match &input.data {
+        syn::Data::Struct(data) => {
+            let top_default_values = get_top_default_values(
+                &top_attributes,
+                &data.fields,
+                Location::Struct,
+                error_tokens,
+            );
+            if let Some(ident) = &top_default_values.options.variants {
+                error!(
+                    error_tokens,
+                    ident.span(),
+                    "`{}` can only be used on enums.",
+                    ident
+                );
+            }
+
+            Some(get_fields_default_values(
+                quote_spanned! { input.ident.span()=> Self },
+                input.ident.to_string(),
+                top_default_values,
+                &data.fields,
+                context,
+                error_tokens,
+            ))
+        }
+        syn::Data::Enum(data) => {
+            get_enum_default_fields(&input.ident, top_attributes.as_slice(), data, context, error_tokens)
+        }
+        syn::Data::Union(_) => None,
     }
 }
 
 pub fn derive(input: &DeriveInput) -> TokenStream2 {
+    if let Some(error_tokens) = reject_union(input) {
+        return error_tokens;
+    }
+
     let mut error_tokens = Vec::new();
 
-    let top_attribute = attrs::find_attribute_unique(
-        &input.attrs,
-        constants::DEFAULT_IDENT,
+    // the errors of the context are reported by the `DefaultWith` derive
+    let context = default_with::get_context(input, &mut Vec::new());
+    let default_fields = get_default_fields(
+        input,
+        context.as_ref().map(|c| &c.ident),
         &mut error_tokens,
+    )
+    // the errors don't prevent the implementation, so the type stays usable beside them
+    .inspect(|f| try_default::check_infallible(&f.options, &mut error_tokens));
+    let cached = default_fields
+        .as_ref()
+        .and_then(|f| f.options.cached.clone())
+        .filter(|cached| cached::check(input, cached, &mut error_tokens));
+
+    let ident = &input.ident;
+    if let Some(default_fields) = &default_fields {
+        default_fields.check_recursion(ident, &mut error_tokens);
+        default_fields.check_constructors(&mut error_tokens);
+
+        // the warnings would only add noise to the errors
+        if error_tokens.is_empty() {
+            default_fields.lint(&mut error_tokens);
+        }
+    }
+
+    let vis = &input.vis;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let error_tokens: TokenStream2 = error_tokens.into_iter().collect();
+
+    let Some(default_fields) = default_fields else {
+        return quote! {
+            impl #impl_generics #DefaultTraitPath for #ident #type_generics #where_clause {
+                fn default() -> Self {
+                    panic!()
+                }
+            }
+
+            #error_tokens
+        };
+    };
+
+    let branches = condition::branches(
+        ident,
+        &default_fields.conditional,
+        default_fields.fallback_body(),
     );
+    let (default_functions, default_ref_function) = match cached {
+        Some(cached) => {
+            let default_body = cached::default_body(&cached);
+            let default_ref_functions = branches.into_iter().map(|Branch { cfg, body }| {
+                let function = cached::default_ref(ident, vis, &cached, body);
+                quote! { #cfg #function }
+            });
 
-    let tokens = match &input.data {
-        syn::Data::Struct(data) => derive_struct(top_attribute, data, &mut error_tokens),
-        syn::Data::Enum(data) => derive_enum(top_attribute, data, &mut error_tokens),
-        syn::Data::Union(data) => {
-            return error!(
-                data.union_token.span(),
-                "this derive is not implemented for unions."
+            (
+                quote! { fn default() -> Self { #default_body } },
+                Some(quote! { #(#default_ref_functions)* }),
             )
-            .into_compile_error();
+        }
+        None => {
+            let default_functions = branches.into_iter().map(|Branch { cfg, body }| {
+                quote! {
+                    #cfg
+                    fn default() -> Self {
+                        #body
+                    }
+                }
+            });
+
+            (quote! { #(#default_functions)* }, None)
         }
     };
 
-    let ident = &input.ident;
-    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
-    let error_tokens: TokenStream2 = error_tokens.into_iter().collect();
+    let profile_functions: Vec<_> = default_fields
+        .profiles
+        .iter()
+        .map(|profile| (profile, profile::constructor_ident(profile)))
+        // the conflicts are reported by `check_constructors`
+        .filter(|(_, fn_ident)| !default_fields.is_conflicting(fn_ident))
+        .map(|(profile, fn_ident)| {
+            let doc = format!("Returns the default value of the `{}` profile.", profile.value());
+            let body = default_fields.body(Selection {
+                profile: Some(profile),
+                ..Default::default()
+            });
+
+            quote! {
+                #[doc = #doc]
+                #vis fn #fn_ident() -> Self {
+                    #body
+                }
+            }
+        })
+        .collect();
+    let in_place_functions = default_fields.in_place_functions(vis);
+    let version_functions = default_fields.version_functions(vis);
+    // the conflicts are reported by `check_constructors`
+    let has_variant_functions =
+        !default_fields.variants.is_empty() && !default_fields.has_conflicting_variant();
+    let variant_functions = has_variant_functions.then(|| {
+        let all = default_fields.options.variants.is_some();
+        variants::functions(ident, vis, &input.generics, &default_fields.variants, all)
+    });
+
+    let inherent_tokens = (!profile_functions.is_empty()
+        || in_place_functions.is_some()
+        || version_functions.is_some()
+        || default_ref_function.is_some()
+        || variant_functions.is_some())
+    .then(|| {
+        quote! {
+            impl #impl_generics #ident #type_generics #where_clause {
+                #(#profile_functions)*
+
+                #variant_functions
+
+                #in_place_functions
+
+                #version_functions
+
+                #default_ref_function
+            }
+        }
+    });
+
+    let marker_tokens = default_fields
+        .options
+        .zeroed
+        .as_ref()
+        .map(|_| zeroed::marker(ident, &input.generics));
 
     quote! {
         impl #impl_generics #DefaultTraitPath for #ident #type_generics #where_clause {
-            fn default() -> Self {
-                #tokens
-            }
+            #default_functions
         }
 
+        #inherent_tokens
+
+        #marker_tokens
+
         #error_tokens
     }
 }
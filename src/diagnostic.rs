@@ -0,0 +1,99 @@
+use std::fmt::Display;
+
+use crate::{Span2, TokenStream2};
+
+/// An error with help messages and notes pointing at other spans.
+///
+/// The proc macros can't emit real notes nor help messages on stable, so they are appended to the message,
+/// the notes with the line of their span.
+pub struct Diagnostic {
+    span: Span2,
+    message: String,
+    helps: Vec<String>,
+    notes: Vec<(Span2, String)>,
+}
+
+impl Diagnostic {
+    pub fn new(span: Span2, message: impl Display) -> Self {
+        Self {
+            span,
+            message: message.to_string(),
+            helps: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// Adds a help message, like a suggestion.
+    pub fn help(mut self, message: impl Display) -> Self {
+        self.helps.push(message.to_string());
+        self
+    }
+
+    /// Adds a note pointing at `span`, like the declaration of a suggested field : `<message> on line <line>`.
+    pub fn span_note(mut self, span: Span2, message: impl Display) -> Self {
+        self.notes.push((span, message.to_string()));
+        self
+    }
+
+    pub fn into_error(self) -> syn::Error {
+        let mut message = self.message;
+        for (span, note) in self.notes {
+            message.push_str(&format!("\n= note: {} on line {}", note, span.unwrap().line()));
+        }
+        for help in self.helps {
+            message.push_str("\n= help: ");
+            message.push_str(&help);
+        }
+
+        syn::Error::new(self.span, message)
+    }
+
+    pub fn emit(self, error_tokens: &mut Vec<TokenStream2>) {
+        error_tokens.push(self.into_error().into_compile_error());
+    }
+}
+
+/// The edit distance between `a` and `b` : the number of insertions, deletions, substitutions
+/// and transpositions of adjacent characters turning `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] is the distance between the first `i` characters of `a` and the first `j` ones of `b`
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = substitution
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// Finds the candidate closest to `name`, if it's close enough to be a typo.
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
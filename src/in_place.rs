@@ -0,0 +1,100 @@
+use quote::{format_ident, quote};
+use syn::Type;
+
+use crate::{
+    constants::{AddrOfMutPath, AllocCrate, PtrWritePath, WriteBytesPath},
+    default::DefaultValue,
+    types, zeroed, Span2, TokenStream2,
+};
+
+/// Writes the default value of `ty` at `ptr`.
+///
+/// The arrays are written element by element, so that large arrays never go through the stack.
+fn write_default(ptr: TokenStream2, ty: &Type, auto: bool) -> TokenStream2 {
+    let Type::Array(array) = types::strip(ty) else {
+        let value = types::default_value(ty, auto);
+        return quote! { #PtrWritePath(#ptr, #value); };
+    };
+
+    let elem = &array.elem;
+    let len = &array.len;
+    let elem_ptr = format_ident!("ptr", span = Span2::mixed_site());
+    let index = format_ident!("i", span = Span2::mixed_site());
+    let write_elem = write_default(quote! { #elem_ptr.add(#index) }, elem, auto);
+
+    quote! {
+        let #elem_ptr = (#ptr).cast::<#elem>();
+        for #index in 0..(#len) {
+            #write_elem
+        }
+    }
+}
+
+/// Builds the body of `default_in_place`, writing each field at its place in `slot`.
+///
+/// With `zeroed`, the whole value is zeroed first, then only the overridden fields are written.
+pub fn build(slot: &syn::Ident, values: Vec<DefaultValue>, zeroed: bool) -> TokenStream2 {
+    let zeroed_tokens = zeroed.then(|| {
+        let assertions = zeroed::assertions(&values);
+        quote! {
+            #assertions
+            #WriteBytesPath(#slot, 0, 1);
+        }
+    });
+
+    let writes = values
+        .iter()
+        .filter(|value| !zeroed || value.is_override)
+        .map(|value| {
+            let member = value.member();
+            let ptr = quote! { #AddrOfMutPath!((*#slot).#member) };
+            let ty = &value.ty;
+            let cfgs = &value.cfgs;
+
+            if value.in_place {
+                quote! { #(#cfgs)* <#ty>::default_in_place(#ptr); }
+            } else if value.is_override {
+                let field_value = &value.value;
+                quote! { #(#cfgs)* #PtrWritePath(#ptr, #field_value); }
+            } else {
+                // the scope keeps the bindings of the arrays apart
+                let write = write_default(ptr, ty, value.auto);
+                quote! { #(#cfgs)* { #write } }
+            }
+        });
+
+    quote! {
+        #zeroed_tokens
+        #(#writes)*
+    }
+}
+
+/// Generates `default_in_place` and `default_boxed`.
+pub fn functions(vis: &syn::Visibility, body: TokenStream2, slot: &syn::Ident) -> TokenStream2 {
+    quote! {
+        /// Writes the default value into `slot`, field by field, without building it on the stack.
+        ///
+        /// If a default value panics, the fields already written are leaked.
+        ///
+        /// # Safety
+        ///
+        /// `slot` must be valid for writes and properly aligned. The previous value isn't dropped.
+        #[allow(unused_unsafe)]
+        #vis unsafe fn default_in_place(#slot: *mut Self) {
+            unsafe {
+                #body
+            }
+        }
+
+        /// Returns the default value allocated on the heap, without building it on the stack.
+        #vis fn default_boxed() -> #AllocCrate::boxed::Box<Self> {
+            let mut boxed = #AllocCrate::boxed::Box::<Self>::new_uninit();
+
+            // SAFETY: the allocation is valid for writes and aligned, and `default_in_place` initializes it
+            unsafe {
+                Self::default_in_place(boxed.as_mut_ptr());
+                boxed.assume_init()
+            }
+        }
+    }
+}
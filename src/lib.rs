@@ -9,19 +9,39 @@
 //! ![Crates.io License](https://img.shields.io/crates/l/better_default)
 //!
 //!
-//! This crate provide a single derive trait called `Default`. This derive act as the std `Default` derive, but allows to modify the default values of each fields. It also allows to mark enum variants with fields as default.
+//! This crate provide a derive trait called `Default`. This derive act as the std `Default` derive, but allows to modify the default values of each fields. It also allows to mark enum variants with fields as default.
 //!
 //! ## Features
 //!  - Does everything the std `Default` derive trait does
 //!  - Support marking enum variant with fields as default
 //!  - Support overriding the default value of each fields
-//!  - Support no-std, which means it will output code which is no-std. **Note that this library by itself needs the std library**.
+//!  - Support several named sets of default values (profiles)
+//!  - Support default values depending on a runtime context
+//!  - Support fallible default values
+//!  - Support arrays and tuples of any size
+//!  - Support default values for some types which don't implement `Default` (pointers, non-zero integers, ...)
+//!  - Support building structs from zeroed memory
+//!  - Support building large structs directly on the heap
+//!  - Support computing the default value once, then cloning it
+//!  - Support modifiers wrapping the default values, like `some` or `into`
+//!  - Support collection literals, like `[1, 2]` or `{"a": 1}`
+//!  - Support default values parsed from strings, checked at compile time for the std types
+//!  - Support duration and byte size literals, like `"30s"` or `"64MiB"`
+//!  - Support range checks and validation functions on the default values
+//!  - Detect the default values which recurse infinitely
+//!  - Warn about the default values which change nothing
+//!  - Generate constructors for the enum variants
+//!  - Select the default variant per configuration or at runtime
+//!  - Use different default values per configuration
+//!  - Support the fields behind `cfg`
+//!  - Support the default values changing from a version on
+//!  - Support no-std, which means it will output code which is no-std (except for the options needing the std library, which are disabled with the `std` feature). **Note that this library by itself needs the std library**.
 //!
 //! See all those features in actions in the `Examples` chapter.
 //!
 //! ## How to use
 //!
-//! > **Before doing anything here**, if you want to override the fields of an enum variant, **you should mark it as default first**
+//! > **Before doing anything here**, if you want to override the fields of an enum variant, **you should mark it as default first** (the overrides of the other variants are only used by their [constructors](#18-variant-constructors))
 //!
 //! ```rust, ignore
 //! use better_default::Default;
@@ -151,210 +171,950 @@
 //! default values while some of the fields have their
 //! own attribute.**
 //!
-//! ## Examples
+//! ### 2. Named profiles
 //!
-//! 1) **The per-field way : Usage of per-field attributes**
+//! A type can have several sets of default values, called profiles. The qualified attributes take the profile name before the value :
+//! ```rust, ignore
+//! #[default(profile = "<name>", <expression>)] // per-field attribute
+//! #[default(profile = "<name>"; (<field_id>: <expression>),*)] // top attribute
+//! ```
 //!
-//! Per field attributes are more suitable for struct / enum variants with named fields.
+//! For each profile, an associated function `default_<name>()` is generated. The fields without a value for this profile keep their unqualified default value, and `Default` only uses the unqualified values.
 //!
 //! ```rust
 //! use better_default::Default;
 //!
-//! #[derive(Default, Debug)]
-//! enum Enum {
-//!     #[default] // mark the variant as default (this is mandatory)
-//!     Variant1 {
-//!         #[default(1)] // set the default value of `first` to 1
-//!         first: u32,
+//! #[derive(Default, Debug, PartialEq)]
+//! #[default(profile = "prod"; workers: 64)]
+//! struct Config {
+//!     #[default(5)]
+//!     #[default(profile = "prod", 50)]
+//!     retries: u32,
 //!
-//!         // keep the default value for `second`
-//!         second: String,
-//!     },
+//!     #[default(4)]
+//!     workers: u32,
+//! }
 //!
-//!     Variant2,
+//! fn main() {
+//!     assert_eq!(Config::default(), Config { retries: 5, workers: 4 });
+//!     assert_eq!(Config::default_prod(), Config { retries: 50, workers: 64 });
+//! }
+//! ```
 //!
-//!     Variant3,
+//! > On enums, the profiled attributes can only be used on the variant marked as default with an unqualified attribute.
+//!
+//! ### 3. Context-aware default values
+//!
+//! Some default values depend on runtime information, which `Default::default()` can't receive. The `DefaultWith` derive generates a `default_with` associated function taking a context, declared with the `#[default_with(<ident>: <type>)]` attribute. The default values can then use the context with the following syntax, in the per-field attributes and in the top attributes :
+//! ```rust, ignore
+//! #[default(<ident> => <expression>)]
+//! ```
+//!
+//! The fields without such a value keep their usual default value, and the fields marked with `#[default_with]` are built with the `default_with` function of their own type, which receives the same context.
+//!
+//! ```rust
+//! use better_default::{Default, DefaultWith};
+//!
+//! struct AppContext {
+//!     cpus: u32,
+//! }
+//!
+//! #[derive(Default, DefaultWith, Debug, PartialEq)]
+//! #[default_with(ctx: &AppContext)]
+//! struct Pool {
+//!     #[default(ctx => ctx.cpus * 2)]
+//!     workers: u32,
+//!
+//!     #[default(10)]
+//!     queue: u32,
 //! }
 //!
 //! fn main() {
-//!     let default = Enum::default();
+//!     let pool = Pool::default_with(&AppContext { cpus: 4 });
+//!     assert_eq!(pool, Pool { workers: 8, queue: 10 });
 //!
-//!     // should print "Variant1 { first: 1, second: "" }"
-//!     println!("{:?}", default);
+//!     // `Default` ignores the values using the context
+//!     assert_eq!(Pool::default(), Pool { workers: 0, queue: 10 });
+//! }
+//! ```
+//!
+//! > Since procedural macro crates can't export traits, `default_with` is an associated function of the type, not the method of a trait.
+//!
+//! ### 4. Fallible default values
+//!
+//! When some default values can fail to be computed, the `TryDefault` derive generates a `try_default` associated function returning a `Result`. The error type is declared in the top attribute with `error = <type>`, and the default values can propagate errors into it with `?` :
+//!
+//! ```rust
+//! use better_default::TryDefault;
+//!
+//! #[derive(TryDefault, Debug)]
+//! #[default(error = std::num::ParseIntError)]
+//! struct Config {
+//!     #[default("8080".parse()?)]
+//!     port: u16,
+//!
+//!     host: String,
+//! }
+//!
+//! fn main() {
+//!     let config = Config::try_default().unwrap();
+//!     assert_eq!(config.port, 8080);
 //! }
 //! ```
 //!
+//! The top attribute also accepts these options :
+//!  - `field_error = <expression>` : a function `fn(&'static str, <error>) -> <error>`, called with the name of the failing field and its error, to wrap the error with the name of the field.
+//!  - `impl_default` : also implements `Default`, which panics with the name of the failing field and its error (which must implement `Debug`).
+//!
+//! ### 5. Tracing panics
+//!
+//! When a default value panics, the panic doesn't tell which field was being computed. With the `trace_panics` option in the top attribute, the fields are computed one by one, and the field being computed is reported when a panic unwinds through it :
+//!
 //! ```rust
 //! use better_default::Default;
 //!
-//! #[derive(Default, Debug)]
-//! // Structs don't need to be mark as default with a top attribute. They're optional.
-//! struct Struct {
-//!     #[default(10)] // set the default value of field1 to be 10
-//!     field1: u32,
+//! #[derive(Default)]
+//! #[default(trace_panics)]
+//! struct Config {
+//!     #[default("80a".parse().unwrap())] // panics, then prints "while computing default of Config::port"
+//!     port: u16,
+//! }
+//! ```
 //!
-//!     // keeps the usual default value for field2
-//!     field2: String,
+//! > The report needs the std library, so without the `std` feature (enabled by default), this option has no effect.
+//!
+//! ### 6. Arrays and tuples
+//!
+//! The fields without a default value which are arrays longer than 32 elements, arrays with a generic length or tuples longer than 12 elements are built element by element, since core doesn't implement `Default` for them. The shorter ones keep using their `Default` implementation. The types are detected syntactically, so this doesn't apply through type aliases.
+//!
+//! An array can also be built from the index of each element, with `each = <closure>` :
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default)]
+//! struct Buffers<const N: usize> {
+//!     samples: [f32; N],
+//!
+//!     #[default(each = |i| i as u8)]
+//!     ramp: [u8; 256],
 //! }
 //!
 //! fn main() {
-//!     let default = Struct::default();
-//!     println!("{:?}", default) // should print "Struct { field1: 10, field2: "" }"
+//!     let buffers = Buffers::<64>::default();
+//!     assert_eq!(buffers.samples, [0.0; 64]);
+//!     assert_eq!(buffers.ramp[200], 200);
+//! }
+//! ```
+//!
+//! ### 7. Types without a `Default` implementation
+//!
+//! Some types don't implement `Default`, but have a sensible default value. With `#[default(auto)]` on a field, or the `auto` option in the top attribute for all the fields without a default value, these types get this value :
+//!
+//! | Type                                      | Default value               |
+//! |-------------------------------------------|-----------------------------|
+//! | `*const T`, `*mut T`                      | a null pointer              |
+//! | `NonNull<T>`                              | `NonNull::dangling()`       |
+//! | `NonZeroU32`, `NonZero<T>`, ...           | `MIN`                       |
+//! | `&str`                                    | `""`                        |
+//! | `&[T]`, `&mut [T]`                        | an empty slice              |
+//! | `Option<T>` (like `Option<&T>`)           | `None`                      |
+//!
+//! The other types keep their `Default` value, and the function pointers, which have no sensible default value, are reported as an error. Like the arrays and the tuples, the types are detected syntactically.
+//!
+//! ```rust
+//! use better_default::Default;
+//! use std::num::NonZeroU32;
+//!
+//! #[derive(Default)]
+//! #[default(auto)]
+//! struct Handle {
+//!     id: NonZeroU32,
+//!     name: &'static str,
+//!     raw: *const u8,
 //! }
 //!
+//! fn main() {
+//!     let handle = Handle::default();
+//!     assert_eq!(handle.id.get(), 1);
+//!     assert_eq!(handle.name, "");
+//!     assert!(handle.raw.is_null());
+//! }
 //! ```
 //!
-//! While not recommended, you can also use them on unnamed fields :
+//! ### 8. Zeroed structs
+//!
+//! For large structs, like the ones shared with C code, the `zeroed` option in the top attribute builds the struct from zeroed memory (`core::mem::zeroed()`), then assigns the fields with a default value :
 //!
 //! ```rust
 //! use better_default::Default;
+//! use std::ffi::{c_char, c_int};
 //!
-//! #[derive(Default, Debug)]
-//! // Structs don't need to be mark as default with a top attribute. They're optional.
-//! struct Struct (
-//!     #[default(10)] // set the default value of field1 to be 10
-//!     u32,
+//! #[derive(Default)]
+//! #[default(zeroed, version: 2)]
+//! #[repr(C)]
+//! struct Header {
+//!     version: c_int,
+//!     name: *const c_char,
+//!     reserved: [u8; 256],
+//! }
 //!
-//!     // keeps the usual default value for field2
-//!     String,
-//! );
+//! #[derive(Default)]
+//! #[default(zeroed)]
+//! #[repr(C)]
+//! struct Packet {
+//!     header: Header,
+//!     previous: Header,
+//!     #[default(1500)]
+//!     size: u32,
+//! }
 //!
 //! fn main() {
-//!     let default = Struct::default();
-//!     println!("{:?}", default) // should print "Struct(10, "")"
+//!     let packet = Packet::default();
+//!     assert_eq!(packet.header.version, 0);
+//!     assert_eq!(packet.size, 1500);
+//!
+//!     let header = Header::default();
+//!     assert_eq!(header.version, 2);
+//!     assert!(header.name.is_null());
 //! }
 //! ```
 //!
+//! The fields without a default value stay zeroed, including the nested structs, whose own default values aren't used.
+//!
+//! Since the zeroed memory must be a valid value of every field, `zeroed` is only accepted when the types of all the fields are known to be valid with all their bytes at zero :
+//!  - the integers, the floats, `bool`, `char` and their FFI aliases (`c_int`, ...), `PhantomData` and `MaybeUninit`
+//!  - the raw pointers
+//!  - the `Option`s of references, function pointers, `Box`, `NonNull` and the non-zero integers
+//!  - the arrays and tuples of those types
+//!  - the structs which use `zeroed` too
+//!
+//! The other types, like the references or the non-zero integers, are reported as errors.
+//!
+//! ### 9. Building large values in place
+//!
+//! Building a large value with `Box::new(Type::default())` builds it on the stack first, which can overflow it. With the `in_place` option in the top attribute of a struct, two more associated functions are generated :
+//!  - `default_boxed() -> Box<Self>`, which allocates the value on the heap, then writes its default value in it.
+//!  - `unsafe fn default_in_place(slot: *mut Self)`, which writes the default value at `slot`, field by field.
+//!
+//! The arrays without a default value are written element by element, and the fields marked with `#[default(in_place)]` are written with the `default_in_place` function of their own type, so that the value is never built on the stack :
+//!
 //! ```rust
 //! use better_default::Default;
 //!
-//! #[derive(Default, Debug)]
-//! enum Enum {
-//!     #[default] // mark the variant as default (this is mandatory)
-//!     Variant1 (
-//!         #[default(1)] // set the default value to 1
-//!         u32,
+//! #[derive(Default)]
+//! #[default(in_place)]
+//! struct Buffers {
+//!     samples: [f32; 1 << 20],
+//! }
 //!
-//!         // keep the default value
-//!         String,
-//!     ),
+//! #[derive(Default)]
+//! #[default(in_place)]
+//! struct State {
+//!     #[default(in_place)]
+//!     buffers: Buffers,
 //!
-//!     Variant2,
+//!     #[default(1)]
+//!     version: u32,
+//! }
 //!
-//!     Variant3,
+//! fn main() {
+//!     let state = State::default_boxed();
+//!     assert_eq!(state.buffers.samples[1000], 0.0);
+//!     assert_eq!(state.version, 1);
+//! }
+//! ```
+//!
+//! > `default_boxed` uses the `Box` of the std library, or, without the `std` feature, the one of the `alloc` crate, which must be declared with `extern crate alloc;`.
+//!
+//! ### 10. Cached default values
+//!
+//! When the default value is expensive to compute and often needed, the `cached` option in the top attribute computes it once, the first time it's needed, and stores it in a static. `default()` then clones it, and the generated `default_ref() -> &'static Self` gives a reference to it :
+//!
+//! ```rust
+//! use better_default::Default;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Default, Clone)]
+//! #[default(cached)]
+//! struct Config {
+//!     #[default((0..1000).map(|i| (i.to_string(), i)).collect())]
+//!     table: HashMap<String, u32>,
 //! }
 //!
 //! fn main() {
-//!     let default = Enum::default();
+//!     let config = Config::default();
+//!     assert_eq!(config.table["42"], 42);
 //!
-//!     // should print "Variant1(1, "")"
-//!     println!("{:?}", default);
+//!     let shared: &'static Config = Config::default_ref();
+//!     assert_eq!(shared.table.len(), 1000);
 //! }
 //! ```
 //!
-//! 2) **The all at once way : Usage of top default attributes**
+//! Since the value is shared between threads and cloned, the type must implement `Clone`, `Send` and `Sync`, and it can't be generic.
 //!
-//! The particularity of the top attribute is that you can define all the default values at the same place.
+//! > The static uses `std::sync::OnceLock`, so this option requires the `std` feature (enabled by default).
 //!
-//! > **Not all the fields need to be represented here, only those you want to modify.**
+//! ### 11. Modifiers
+//!
+//! A default value can be preceded by modifiers, which wrap the expression following them :
+//!
+//! | Modifier         | Generated expression      |
+//! |------------------|---------------------------|
+//! | `into <expr>`    | `Into::into(<expr>)`      |
+//! | `some <expr>`    | `Some(<expr>)`            |
+//! | `boxed <expr>`   | `Box::new(<expr>)`        |
+//! | `arc <expr>`     | `Arc::new(<expr>)`        |
+//! | `rc <expr>`      | `Rc::new(<expr>)`         |
+//!
+//! The modifiers can be chained, like `some into "text"`, which gives `Some(Into::into("text"))`. The `into_all` option in the top attribute converts all the default values with `Into`, like the `into` modifier.
 //!
 //! ```rust
 //! use better_default::Default;
 //!
-//! #[derive(Default, Debug)]
-//! enum Enum {
-//!     // mark the variant as default, and also specifies the default values :
-//!     //      - the first field keeps it's usual default value.
-//!     //      - the second field (which is at index 1) will have it's default value set to "Hello world!"
-//!     #[default(1: "Hello world!".to_string())]
-//!     Variant1(u32, String),
+//! trait Handler {}
+//! struct Logger;
+//! impl Handler for Logger {}
 //!
-//!     Variant2,
+//! #[derive(Default)]
+//! #[default(host: into "localhost")]
+//! struct Server {
+//!     host: String,
 //!
-//!     Variant3,
+//!     #[default(some 8080)]
+//!     port: Option<u16>,
+//!
+//!     #[default(boxed Logger)]
+//!     handler: Box<dyn Handler>,
 //! }
 //!
 //! fn main() {
-//!     let default = Enum::default();
+//!     let server = Server::default();
+//!     assert_eq!(server.host, "localhost");
+//!     assert_eq!(server.port, Some(8080));
+//! }
+//! ```
 //!
-//!     // should print "Variant1(0, "Hello world!")"
-//!     println!("{:?}", default);
+//! A modifier must be followed by a literal, a path, a block or a collection literal (see below), so that `some(5)` stays a call to a function named `some`, and `some - 1` a subtraction. The other values, like the negative numbers, are written without the modifier, like `Some(-1)`.
+//!
+//! > Without the `std` feature, the `boxed`, `arc` and `rc` modifiers use the `alloc` crate, which must be declared with `extern crate alloc;`.
+//!
+//! ### 12. Collection literals
+//!
+//! The collections can be written as literals :
+//!  - `[<element>, ...]`, like `[1, 2, 3]` for a `Vec<u32>`, or `["a", "b"]` for a `HashSet<String>`.
+//!  - `{<key>: <value>, ...}` for the maps, like `{"a": 1, "b": 2}` for a `HashMap<String, u32>`.
+//!
+//! They generate `IntoIterator::into_iter([...]).collect()`, with the elements, the keys and the values converted with `Into` (except for the number literals, which take the type of the collection elements directly). The maps can't have the same key twice.
+//!
+//! ```rust
+//! use better_default::Default;
+//! use std::collections::{BTreeMap, HashMap};
+//!
+//! #[derive(Default)]
+//! #[default(ports: [80, 443])]
+//! struct Config {
+//!     ports: Vec<u16>,
+//!
+//!     #[default(["localhost", "127.0.0.1"])]
+//!     hosts: Vec<String>,
+//!
+//!     #[default({"soft": 10, "hard": 20})]
+//!     limits: HashMap<String, u32>,
+//!
+//!     #[default({1: "admin", 2: "user"})]
+//!     roles: BTreeMap<u8, String>,
+//!
+//!     #[default([1, 2])]
+//!     array: [u8; 2], // the arrays stay arrays
+//! }
+//!
+//! fn main() {
+//!     let config = Config::default();
+//!     assert_eq!(config.ports, [80, 443]);
+//!     assert_eq!(config.limits["hard"], 20);
+//!     assert_eq!(config.roles[&1], "admin");
 //! }
 //! ```
 //!
+//! An array literal is collected into any type implementing `FromIterator`, like `Vec`, `HashSet` or `Box<[T]>`. It stays an array for the array types, even behind an alias, and when it follows the `into` modifier. The literals nested in the elements aren't collected, they are converted with `Into` like the other elements.
+//!
+//! ### 13. Parsed default values
+//!
+//! The default value can be written as a string with `parse = "<string>"`, which is parsed into the field type :
+//!
 //! ```rust
 //! use better_default::Default;
+//! use std::net::{IpAddr, SocketAddr};
+//! use std::path::PathBuf;
 //!
-//! #[derive(Default, Debug)]
-//! // here we can use the top default attribute to customize the default values of our fields.
-//! //      - we change the default value of the first field (represented by the index 0) to 1
-//! #[default(0: 1, 1: "a".to_string())]
-//! struct Struct(u32, String);
+//! #[derive(Default)]
+//! #[default(ip: parse = "::1")]
+//! struct Server {
+//!     #[default(parse = "127.0.0.1:8080")]
+//!     addr: SocketAddr,
+//!
+//!     ip: IpAddr,
+//!
+//!     #[default(parse = "/var/log")]
+//!     logs: PathBuf,
+//! }
 //!
 //! fn main() {
-//!     let default = Struct::default();
-//!     println!("{:?}", default) // should print "Struct(1, "a")"
+//!     let server = Server::default();
+//!     assert_eq!(server.addr.port(), 8080);
 //! }
 //! ```
 //!
-//! This can also be used on named fields :
+//! For the integers, the floats, `bool`, `char`, and the addresses of `core::net` (`IpAddr`, `Ipv4Addr`, `Ipv6Addr`, `SocketAddr`, `SocketAddrV4` and `SocketAddrV6`), the string is parsed at compile time, an invalid string being reported as an error, and the value is built directly, without parsing it at runtime. The other types are parsed at runtime with `FromStr`, panicking with the name of the field if the string is invalid.
+//!
+//! ### 14. Durations and byte sizes
+//!
+//! The durations and the byte sizes can be written as strings, which are converted at compile time :
+//!  - `duration = "<duration>"` gives a `Duration`, with the units `ms`, `s`, `m`, `h` and `d`, like `"30s"`, `"1.5ms"` or `"1h 30m"`.
+//!  - `bytes = "<size>"` gives an integer, with the units `B`, `KB`, `KiB`, `MB`, `MiB`, `GB` and `GiB`, like `"64MiB"`. When the field type is an integer type, the size is checked to fit in it.
 //!
 //! ```rust
 //! use better_default::Default;
+//! use std::time::Duration;
 //!
-//! #[derive(Default, Debug)]
-//! enum Enum {
-//!     // mark the variant as default, and also specifies the default values :
-//!     //      - the first field keeps it's usual default value.
-//!     //      - the second field (field2) will have it's default value set to "Hello world!"
-//!     #[default(field2: "Hello world!".to_string())]
-//!     Variant1 {
-//!         field1: u32,
-//!         field2: String
-//!     },
+//! #[derive(Default)]
+//! #[default(idle: duration = "1h 30m")]
+//! struct Limits {
+//!     #[default(duration = "30s")]
+//!     timeout: Duration,
 //!
-//!     Variant2,
+//!     idle: Duration,
 //!
-//!     Variant3,
+//!     #[default(some duration = "250ms")]
+//!     grace: Option<Duration>,
+//!
+//!     #[default(bytes = "64MiB")]
+//!     max_body: u64,
 //! }
 //!
 //! fn main() {
-//!     let default = Enum::default();
+//!     let limits = Limits::default();
+//!     assert_eq!(limits.timeout, Duration::from_secs(30));
+//!     assert_eq!(limits.max_body, 64 * 1024 * 1024);
+//! }
+//! ```
 //!
-//!     // should print "Variant1 { 0, "Hello world!" }"
-//!     println!("{:?}", default);
+//! Like the expressions, the strings of `parse`, `duration` and `bytes` can follow modifiers, like `some`.
+//!
+//! ### 15. Validation
+//!
+//! A field value can be followed by `range = <range>`, checking that it's in the range. When the value and the bounds are literals which can be compared exactly, the check is done at compile time, otherwise the value is checked with `debug_assert!`.
+//!
+//! The `validate = <function>` option of the top attribute checks the whole value once it's built, with a function taking `&Self` and returning a `Result<(), E>`, where `E` implements `Display`. In debug builds, an error panics with a message naming the type.
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default)]
+//! #[default(validate = Self::check)]
+//! struct Pool {
+//!     #[default(8080, range = 1..=65535)]
+//!     port: u32,
+//!
+//!     #[default(2, range = 1..)]
+//!     min_workers: u32,
+//!
+//!     #[default(8)]
+//!     max_workers: u32,
+//! }
+//!
+//! impl Pool {
+//!     fn check(&self) -> Result<(), String> {
+//!         if self.min_workers > self.max_workers {
+//!             return Err("`min_workers` is greater than `max_workers`".to_string());
+//!         }
+//!
+//!         Ok(())
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let pool = Pool::default();
+//!     assert_eq!(pool.port, 8080);
 //! }
 //! ```
 //!
+//! `range` can only follow an expression, in a field attribute. The validation also runs on the profiles, and at the end of `default_in_place`.
+//!
+//! ### 16. Recursion checks
+//!
+//! A default value calling the `Default` implementation it's part of recurses infinitely, and overflows the stack at runtime. The derive reports those default values :
+//!  - the calls to `Self::default()`, `<Self as Default>::default()` or `<type>::default()` in a default value.
+//!  - the calls to `Default::default()` building a `Self`, like `boxed Default::default()` on a `Box<Self>` field.
+//!  - the fields without a default value whose type holds the type itself, like `Box<Self>` or `(u32, Rc<Self>)`. The types like `Option<Box<Self>>` or `Vec<Self>` are fine, since their default value is empty.
+//!
+//! ```rust, compile_fail
+//! use better_default::Default;
+//!
+//! #[derive(Default)]
+//! enum Expr {
+//!     #[default]
+//!     Negate(Box<Expr>), // error : `Box<Expr>` builds an `Expr` again
+//!     Literal(i32),
+//! }
+//! ```
+//!
+//! The checks are syntactic, so they can be disabled with `allow(recursion)` in the top attribute, for example when the recursion stops at runtime.
+//!
 //! ```rust
 //! use better_default::Default;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
 //!
-//! #[derive(Default, Debug)]
-//! #[default(field1: 1, field2: "Hello world!".to_string())]
-//! struct Struct {
-//!     field1: u32,
-//!     field2: String
+//! static LINKS: AtomicUsize = AtomicUsize::new(0);
+//!
+//! #[derive(Default)]
+//! #[default(allow(recursion))]
+//! struct Chain {
+//!     #[default(if LINKS.fetch_add(1, Ordering::Relaxed) < 2 { Some(Box::new(Self::default())) } else { None })]
+//!     next: Option<Box<Chain>>,
 //! }
 //!
 //! fn main() {
-//!     let default = Struct::default();
-//!     println!("{:?}", default) // should print "Struct { field1: 1, field2: "Hello world!" }"
+//!     let chain = Chain::default();
+//!     assert!(chain.next.unwrap().next.unwrap().next.is_none());
 //! }
 //! ```
 //!
-//! ## Contributing
+//! ### 17. Redundant default values
 //!
-//! You can contribute to the project by making a pull request.
+//! The derive warns about the default values which change nothing, each warning being a lint which can be disabled with `allow(<lint>, ...)` in the top attribute :
+//!  - `redundant` : the value is the default value of the field type, like `0` for an integer, `false`, `None`, `""`, `String::new()`, `vec![]`, `[]` for a collection or `Default::default()`.
+//!  - `redundant_profile` : the value of a profile is the same as the unqualified value, which the profile already falls back to.
 //!
-//! Here are the tools i use for this library :
+//! ```rust
+//! use better_default::Default;
 //!
-//! - [rustdoc-include](https://github.com/frozenlib/rustdoc-include), which allows me to import the readme directly into the `lib.rs` without copying. That's why you can see those `// #[include_doc(...)]` in `lib.rs`. Use the `build_crate_doc` script in the `scripts` folder to update them.
+//! #[derive(Default)]
+//! struct Config {
+//!     #[default(0)] // warning : `0` is already the default value of `u16`
+//!     port: u16,
 //!
-//! ## License
+//!     #[default(3)]
+//!     #[default(profile = "prod", 3)] // warning : the profile already falls back to `3`
+//!     retries: u32,
+//! }
 //!
-//! Licensed under Apache 2.0.
-// #![include_doc("../README.md", end)]
-
+//! #[derive(Default)]
+//! #[default(allow(redundant))]
+//! struct Quiet {
+//!     #[default(0)] // no warning
+//!     port: u16,
+//! }
+//! fn main() {}
+//! ```
+//!
+//! Stable proc macros can't emit warnings, so the warnings are the deprecation warnings of constants named after the lints, which point at the default values. They can also be disabled with `#[allow(deprecated)]`, but this disables the deprecation warnings of the whole module.
+//!
+//! ### 18. Variant constructors
+//!
+//! The variants of an enum with default values on their fields, other than the default variant, get a `default_<variant>()` constructor, the name of the variant being converted to snake case, which builds them with these values. With the `variants` option in the top attribute, every variant gets a constructor, and `variant_defaults()` returns all of them in declaration order, for example to fill a dropdown. `Default` still returns the variant marked as default.
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug, PartialEq)]
+//! #[default(variants)]
+//! enum Shape {
+//!     #[default]
+//!     Circle {
+//!         #[default(1.0)]
+//!         radius: f32,
+//!     },
+//!
+//!     Rectangle {
+//!         #[default(2.0)]
+//!         width: f32,
+//!         #[default(3.0)]
+//!         height: f32,
+//!     },
+//!
+//!     HTTPIcon,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Shape::default(), Shape::default_circle());
+//!     assert_eq!(Shape::default_rectangle(), Shape::Rectangle { width: 2.0, height: 3.0 });
+//!     assert_eq!(Shape::default_http_icon(), Shape::HTTPIcon);
+//!     assert_eq!(Shape::variant_defaults().len(), 3);
+//! }
+//! ```
+//!
+//! The options of the top attribute of the enum, like `into_all` or `validate`, apply to every variant, but the profiles can only be used in the default variant. The constructor of a variant with a field of a generic type only requires this type to implement `Default`. Without the `variants` option, the variants without default values are left as they are, so like with the std derive, their fields don't need to implement `Default`.
+//!
+//! Since a top attribute on a variant marks it as a default variant, the values of the other variants can only be given in the attributes of their fields. A variant whose constructor has the name of another generated function, like `Ref` with the `cached` option, which generates `default_ref`, is reported.
+//!
+//! ### 19. Conditional default variants
+//!
+//! Several variants can be marked as default with a condition, placed before their default values like a profile :
+//!  - `#[default(cfg(<predicate>))]` selects the variant at compile time, in the configurations matching the predicate.
+//!  - `#[default(when = <expression>)]` selects the variant at runtime, when the expression is `true`.
+//!
+//! The conditions are checked in declaration order, the first matching variant being the default one, and the variant marked with an unconditional `#[default]` is the fallback when none of them holds.
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug, PartialEq)]
+//! enum Backend {
+//!     #[default(cfg(target_os = "linux"))]
+//!     Epoll,
+//!
+//!     #[default(cfg(windows))]
+//!     Iocp,
+//!
+//!     #[default(when = std::env::var_os("FORCE_SELECT").is_some())]
+//!     Select,
+//!
+//!     #[default]
+//!     Poll,
+//! }
+//!
+//! fn main() {
+//!     let backend = Backend::default();
+//!
+//!     if cfg!(target_os = "linux") {
+//!         assert_eq!(backend, Backend::Epoll);
+//!     }
+//! }
+//! ```
+//!
+//! The derive generates a `fn default()` per `cfg` condition, guarded by this condition. Without an unconditional variant, the configurations matching none of the `cfg` conditions fail to compile, and the `when` conditions always need one. The profiles can only be used in the unconditional variant, and the conditional default variants are only supported by the `Default` derive.
+//!
+//! ### 20. Values per configuration
+//!
+//! A default value can be preceded by values for some configurations, each one keyed by a `cfg` predicate : `cfg(<predicate>) = <value>, ..., <fallback>`. The first value whose predicate holds is used, and the fallback value, which is mandatory, is used in the other configurations. This works in the field attributes and in the top attributes.
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! #[default(directory: cfg(windows) = "C:\\logs", "/var/log")]
+//! struct Logging {
+//!     #[default(cfg(debug_assertions) = 3, 1)]
+//!     verbosity: u8,
+//!
+//!     #[default(cfg(feature = "compression") = Some(Codec::zstd()), None)]
+//!     codec: Option<Codec>,
+//!
+//!     directory: &'static str,
+//! }
+//!
+//! #[derive(Debug)]
+//! struct Codec;
+//!
+//! impl Codec {
+//!     #[cfg(feature = "compression")]
+//!     fn zstd() -> Self {
+//!         Codec
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let logging = Logging::default();
+//!     assert_eq!(logging.verbosity, if cfg!(debug_assertions) { 3 } else { 1 });
+//! }
+//! ```
+//!
+//! The values are put in blocks gated by `#[cfg]` attributes, so only the value of the current configuration is type-checked, and the values of the other configurations can use items which don't exist in this one. A `range` applies to the values of every configuration.
+//!
+//! ### 21. Fields behind `cfg`
+//!
+//! The `cfg` attributes of the fields are put on the code generated for them. A field removed by its `cfg` doesn't reach the derive at all, so its default value in a top attribute must be guarded by the same `cfg`, to not be reported as an unknown field :
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! #[default(#[cfg(feature = "tls")] tls_cert: "cert.pem".to_string(), port: 443)]
+//! struct Server {
+//!     #[cfg(feature = "tls")]
+//!     tls_cert: String,
+//!
+//!     port: u16,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Server::default().port, 443);
+//! }
+//! ```
+//!
+//! The guards are checked by the compiler : the field is reported as unknown only in the configurations where its guards hold, and a guarded value of a field which exists while its guards don't hold is an error too.
+//!
+//! ### 22. Versioned default values
+//!
+//! A field can change its default value from a version on, while keeping the older values, for example to load the configurations written with an older version. The versioned values take the version before the value, and are declared from the oldest to the newest :
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug, PartialEq)]
+//! struct Config {
+//!     #[default(50)]
+//!     #[default(since = 3, 100)]
+//!     #[default(since = 5, 200)]
+//!     timeout: u32,
+//!
+//!     #[default(since = 2, true)]
+//!     compress: bool,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Config::DEFAULTS_VERSION, 5);
+//!     assert_eq!(Config::default(), Config { timeout: 200, compress: true });
+//!
+//!     assert_eq!(Config::default_for_version(0), Config { timeout: 50, compress: false });
+//!     assert_eq!(Config::default_for_version(4), Config { timeout: 100, compress: true });
+//! }
+//! ```
+//!
+//! The derive generates `default_for_version(version: u32)`, which gives each field its newest value whose version is at most `version`, the value without `since` being the value of the version 0, and `DEFAULTS_VERSION`, the newest version of the type. `Default` and the profiles use the newest values. The versions of a field must be increasing, and the versioned values can only be used in the default variant of an enum without conditional default variants.
+//!
+//! ## Examples
+//!
+//! 1) **The per-field way : Usage of per-field attributes**
+//!
+//! Per field attributes are more suitable for struct / enum variants with named fields.
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! enum Enum {
+//!     #[default] // mark the variant as default (this is mandatory)
+//!     Variant1 {
+//!         #[default(1)] // set the default value of `first` to 1
+//!         first: u32,
+//!
+//!         // keep the default value for `second`
+//!         second: String,
+//!     },
+//!
+//!     Variant2,
+//!
+//!     Variant3,
+//! }
+//!
+//! fn main() {
+//!     let default = Enum::default();
+//!
+//!     // should print "Variant1 { first: 1, second: "" }"
+//!     println!("{:?}", default);
+//! }
+//! ```
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! // Structs don't need to be mark as default with a top attribute. They're optional.
+//! struct Struct {
+//!     #[default(10)] // set the default value of field1 to be 10
+//!     field1: u32,
+//!
+//!     // keeps the usual default value for field2
+//!     field2: String,
+//! }
+//!
+//! fn main() {
+//!     let default = Struct::default();
+//!     println!("{:?}", default) // should print "Struct { field1: 10, field2: "" }"
+//! }
+//!
+//! ```
+//!
+//! While not recommended, you can also use them on unnamed fields :
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! // Structs don't need to be mark as default with a top attribute. They're optional.
+//! struct Struct (
+//!     #[default(10)] // set the default value of field1 to be 10
+//!     u32,
+//!
+//!     // keeps the usual default value for field2
+//!     String,
+//! );
+//!
+//! fn main() {
+//!     let default = Struct::default();
+//!     println!("{:?}", default) // should print "Struct(10, "")"
+//! }
+//! ```
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! enum Enum {
+//!     #[default] // mark the variant as default (this is mandatory)
+//!     Variant1 (
+//!         #[default(1)] // set the default value to 1
+//!         u32,
+//!
+//!         // keep the default value
+//!         String,
+//!     ),
+//!
+//!     Variant2,
+//!
+//!     Variant3,
+//! }
+//!
+//! fn main() {
+//!     let default = Enum::default();
+//!
+//!     // should print "Variant1(1, "")"
+//!     println!("{:?}", default);
+//! }
+//! ```
+//!
+//! 2) **The all at once way : Usage of top default attributes**
+//!
+//! The particularity of the top attribute is that you can define all the default values at the same place.
+//!
+//! > **Not all the fields need to be represented here, only those you want to modify.**
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! enum Enum {
+//!     // mark the variant as default, and also specifies the default values :
+//!     //      - the first field keeps it's usual default value.
+//!     //      - the second field (which is at index 1) will have it's default value set to "Hello world!"
+//!     #[default(1: "Hello world!".to_string())]
+//!     Variant1(u32, String),
+//!
+//!     Variant2,
+//!
+//!     Variant3,
+//! }
+//!
+//! fn main() {
+//!     let default = Enum::default();
+//!
+//!     // should print "Variant1(0, "Hello world!")"
+//!     println!("{:?}", default);
+//! }
+//! ```
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! // here we can use the top default attribute to customize the default values of our fields.
+//! //      - we change the default value of the first field (represented by the index 0) to 1
+//! #[default(0: 1, 1: "a".to_string())]
+//! struct Struct(u32, String);
+//!
+//! fn main() {
+//!     let default = Struct::default();
+//!     println!("{:?}", default) // should print "Struct(1, "a")"
+//! }
+//! ```
+//!
+//! This can also be used on named fields :
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! enum Enum {
+//!     // mark the variant as default, and also specifies the default values :
+//!     //      - the first field keeps it's usual default value.
+//!     //      - the second field (field2) will have it's default value set to "Hello world!"
+//!     #[default(field2: "Hello world!".to_string())]
+//!     Variant1 {
+//!         field1: u32,
+//!         field2: String
+//!     },
+//!
+//!     Variant2,
+//!
+//!     Variant3,
+//! }
+//!
+//! fn main() {
+//!     let default = Enum::default();
+//!
+//!     // should print "Variant1 { 0, "Hello world!" }"
+//!     println!("{:?}", default);
+//! }
+//! ```
+//!
+//! ```rust
+//! use better_default::Default;
+//!
+//! #[derive(Default, Debug)]
+//! #[default(field1: 1, field2: "Hello world!".to_string())]
+//! struct Struct {
+//!     field1: u32,
+//!     field2: String
+//! }
+//!
+//! fn main() {
+//!     let default = Struct::default();
+//!     println!("{:?}", default) // should print "Struct { field1: 1, field2: "Hello world!" }"
+//! }
+//! ```
+//!
+//! ## Optional features
+//!
+//! ### `std`
+//!
+//! Enabled by default. Lets the generated code use the std library for the options which need it, like `trace_panics` and `cached`. Disable it in `no_std` crates.
+//!
+//! ### `clap`
+//!
+//! When a struct derives both `clap::Parser` and `Default`, the `clap_defaults` attribute gives each argument its `better_default` value as `clap` default value, so the `--help` output and `Default` agree, unless the `#[arg(...)]` attribute of the field already sets a default value.
+//!
+//! > **The attribute must be placed above the `derive` attribute.**
+//!
+//! ```rust, ignore
+//! use better_default::Default;
+//! use clap::Parser;
+//!
+//! #[better_default::clap_defaults]
+//! #[derive(Parser, Default)]
+//! struct Cli {
+//!     #[arg(long)]
+//!     #[default("localhost".to_string())] // also becomes `default_value_t`
+//!     host: String,
+//!
+//!     #[arg(long)]
+//!     port: u16, // gets `default_value_t = <u16 as Default>::default()`
+//!
+//!     #[arg(long, default_value_t = 3)] // already has a default value, left untouched
+//!     retries: u32,
+//! }
+//! ```
+//!
+//! `Vec` fields get `default_values_t` instead, and `PathBuf` and `OsString` fields `default_value_os_t`. Since the default parser of `PathBuf` rejects the empty path, a `PathBuf` field without a default value nor a `value_parser` gets a parser accepting it.
+//!
+//! An `Option` field is `None` when its argument is missing, so only a default value written `Some(<value>)` is given to clap, other values being reported. The field types clap can't display are reported too, and the `clap::ValueEnum` types need `#[arg(value_enum)]` to be displayed. `#[command(...)]` fields are left untouched.
+//!
+//! ## Contributing
+//!
+//! You can contribute to the project by making a pull request.
+//!
+//! Here are the tools i use for this library :
+//!
+//! - [rustdoc-include](https://github.com/frozenlib/rustdoc-include), which allows me to import the readme directly into the `lib.rs` without copying. That's why you can see those `// #[include_doc(...)]` in `lib.rs`. Use the `build_crate_doc` script in the `scripts` folder to update them.
+//! - [Miri](https://github.com/rust-lang/miri), which checks the unsafe code generated by the `zeroed` and `in_place` options : `cargo +nightly miri test --test test_in_place --test test_zeroed`.
+//!
+//! ## License
+//!
+//! Licensed under Apache 2.0.
+// #![include_doc("../README.md", end)]
+
 #![warn(missing_docs)]
 
 use proc_macro::TokenStream;
@@ -390,145 +1150,870 @@ macro_rules! handle_error {
 }
 
 mod attrs;
+mod cached;
+#[cfg(feature = "clap")]
+mod clap;
+mod collection;
+mod condition;
+mod constants;
 mod default;
+mod default_with;
 mod derive;
+mod diagnostic;
+mod in_place;
+mod lints;
+mod options;
+mod parsed;
+mod profile;
+mod recursion;
 mod top_attribute;
+mod trace_panics;
 mod traits;
-mod constants;
+mod try_default;
+mod types;
+mod units;
+mod validate;
+mod variants;
+mod versions;
+mod zeroed;
 
 /// The main derive of this crate.
 // #[include_doc("../README.md", start("## How to use"))]
 /// ## How to use
 ///
-/// > **Before doing anything here**, if you want to override the fields of an enum variant, **you should mark it as default first**
+/// > **Before doing anything here**, if you want to override the fields of an enum variant, **you should mark it as default first** (the overrides of the other variants are only used by their [constructors](#18-variant-constructors))
+///
+/// ```rust, ignore
+/// use better_default::Default;
+///
+/// #[derive(Default)]
+/// enum Enum {
+///     #[default]
+///     Variant {
+///         ...
+///     },
+///     ...
+/// }
+/// ```
+///
+/// ### 1. Overriding the default values
+///
+/// There a two ways of overriding the default values : using the per-field attributes or the top default attributes.
+///
+/// #### Per-Field attributes
+///
+/// The per-field attributes are simply attributes you put atop of the fields for which you want to override the default values.
+///
+/// The syntax is the following :
+/// ```rust, ignore
+/// #[default( <expression> )]
+/// <field_ident>: <field_type>
+/// ```
+///
+/// You can put anything you want in the `expression` bloc, **as long as it can be correctly parse by [syn::Expr](https://docs.rs/syn/latest/syn/enum.Expr.html).**
+///
+/// Here is an example of this approach in action :
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug)]
+/// enum Enum {
+///     #[default] // mark the variant as default (this is mandatory)
+///     Variant1 {
+///         #[default(1)] // set the default value of `first` to 1
+///         first: u32,
+///
+///         // keep the default value for `second`
+///         second: String,
+///     },
+///
+///     Variant2,
+///
+///     Variant3,
+/// }
+///
+/// fn main() {
+///     let default = Enum::default();
+///
+///     // should print "Variant1 { first: 1, second: "" }"
+///     println!("{:?}", default);
+/// }
+/// ```
+///
+/// #### Top Default attributes
+///
+/// Instead of placing an attribute on all the fields of a struct / enum variant, you place only on attribute atop of it, containing all the default values overrides.
+///
+/// The syntax of the top attributes is the following :
+/// ```rust, ignore
+/// use better_default::Default;
+///
+/// #[derive(Default)]
+/// #[default((<field_id>: <expression>),*)]
+/// struct Struct {
+///     ...
+/// } // the struct can have unnamed fields
+///
+/// #[derive(Default)]
+/// enum Enum {
+///     #[default((<field_id>: <expression>),*)]
+///     Variant { ... } // the variant can have unnamed fields
+/// }
+/// ```
+///
+/// `field_id` here can means two things : if you deal with named fields, you should put the field ident here. If you deal with unnamed fields, then **you should put the position of the field** *(0 for the first, 1 for the second, etc.)*.
+///
+/// Again, you can put anything you want in the `expression` bloc, **as long as it can be correctly parse by [syn::Expr](https://docs.rs/syn/latest/syn/enum.Expr.html).**
+///
+/// Here are two examples, one covering unnamed fields and one covering named ones.
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug)]
+/// enum Enum {
+///     // mark the variant as default, and also specifies the default values :
+///     //      - the default value of the first field (which is at index 0) is set to 2
+///     //      - the second field (which is at index 1) will have it's default value set to "Hello world!"
+///     #[default(0: 2, 1: "Hello world!".to_string())]
+///     Variant1(u32, String),
+///
+///     Variant2,
+///
+///     Variant3,
+/// }
+///
+/// fn main() {
+///     let default = Enum::default();
+///
+///     // should print "Variant1(2, "Hello world!")"
+///     println!("{:?}", default);
+/// }
+/// ```
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug)]
+/// #[default(field1: 1, field2: "Hello world!".to_string())]
+/// struct Struct {
+///     field1: u32,
+///     field2: String
+/// }
+///
+/// fn main() {
+///     let default = Struct::default();
+///     println!("{:?}", default) // should print "Struct { field1: 1, field2: "Hello world!" }"
+/// }
+/// ```
+///
+/// One last note : **these two approaches can be combined,
+/// which means you can have a top attribute containing some
+/// default values while some of the fields have their
+/// own attribute.**
+///
+/// ### 2. Named profiles
+///
+/// A type can have several sets of default values, called profiles. The qualified attributes take the profile name before the value :
+/// ```rust, ignore
+/// #[default(profile = "<name>", <expression>)] // per-field attribute
+/// #[default(profile = "<name>"; (<field_id>: <expression>),*)] // top attribute
+/// ```
+///
+/// For each profile, an associated function `default_<name>()` is generated. The fields without a value for this profile keep their unqualified default value, and `Default` only uses the unqualified values.
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug, PartialEq)]
+/// #[default(profile = "prod"; workers: 64)]
+/// struct Config {
+///     #[default(5)]
+///     #[default(profile = "prod", 50)]
+///     retries: u32,
+///
+///     #[default(4)]
+///     workers: u32,
+/// }
+///
+/// fn main() {
+///     assert_eq!(Config::default(), Config { retries: 5, workers: 4 });
+///     assert_eq!(Config::default_prod(), Config { retries: 50, workers: 64 });
+/// }
+/// ```
+///
+/// > On enums, the profiled attributes can only be used on the variant marked as default with an unqualified attribute.
+///
+/// ### 3. Context-aware default values
+///
+/// Some default values depend on runtime information, which `Default::default()` can't receive. The `DefaultWith` derive generates a `default_with` associated function taking a context, declared with the `#[default_with(<ident>: <type>)]` attribute. The default values can then use the context with the following syntax, in the per-field attributes and in the top attributes :
+/// ```rust, ignore
+/// #[default(<ident> => <expression>)]
+/// ```
+///
+/// The fields without such a value keep their usual default value, and the fields marked with `#[default_with]` are built with the `default_with` function of their own type, which receives the same context.
+///
+/// ```rust
+/// use better_default::{Default, DefaultWith};
+///
+/// struct AppContext {
+///     cpus: u32,
+/// }
+///
+/// #[derive(Default, DefaultWith, Debug, PartialEq)]
+/// #[default_with(ctx: &AppContext)]
+/// struct Pool {
+///     #[default(ctx => ctx.cpus * 2)]
+///     workers: u32,
+///
+///     #[default(10)]
+///     queue: u32,
+/// }
+///
+/// fn main() {
+///     let pool = Pool::default_with(&AppContext { cpus: 4 });
+///     assert_eq!(pool, Pool { workers: 8, queue: 10 });
+///
+///     // `Default` ignores the values using the context
+///     assert_eq!(Pool::default(), Pool { workers: 0, queue: 10 });
+/// }
+/// ```
+///
+/// > Since procedural macro crates can't export traits, `default_with` is an associated function of the type, not the method of a trait.
+///
+/// ### 4. Fallible default values
+///
+/// When some default values can fail to be computed, the `TryDefault` derive generates a `try_default` associated function returning a `Result`. The error type is declared in the top attribute with `error = <type>`, and the default values can propagate errors into it with `?` :
+///
+/// ```rust
+/// use better_default::TryDefault;
+///
+/// #[derive(TryDefault, Debug)]
+/// #[default(error = std::num::ParseIntError)]
+/// struct Config {
+///     #[default("8080".parse()?)]
+///     port: u16,
+///
+///     host: String,
+/// }
+///
+/// fn main() {
+///     let config = Config::try_default().unwrap();
+///     assert_eq!(config.port, 8080);
+/// }
+/// ```
+///
+/// The top attribute also accepts these options :
+///  - `field_error = <expression>` : a function `fn(&'static str, <error>) -> <error>`, called with the name of the failing field and its error, to wrap the error with the name of the field.
+///  - `impl_default` : also implements `Default`, which panics with the name of the failing field and its error (which must implement `Debug`).
+///
+/// ### 5. Tracing panics
+///
+/// When a default value panics, the panic doesn't tell which field was being computed. With the `trace_panics` option in the top attribute, the fields are computed one by one, and the field being computed is reported when a panic unwinds through it :
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default)]
+/// #[default(trace_panics)]
+/// struct Config {
+///     #[default("80a".parse().unwrap())] // panics, then prints "while computing default of Config::port"
+///     port: u16,
+/// }
+/// ```
+///
+/// > The report needs the std library, so without the `std` feature (enabled by default), this option has no effect.
+///
+/// ### 6. Arrays and tuples
+///
+/// The fields without a default value which are arrays longer than 32 elements, arrays with a generic length or tuples longer than 12 elements are built element by element, since core doesn't implement `Default` for them. The shorter ones keep using their `Default` implementation. The types are detected syntactically, so this doesn't apply through type aliases.
+///
+/// An array can also be built from the index of each element, with `each = <closure>` :
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default)]
+/// struct Buffers<const N: usize> {
+///     samples: [f32; N],
+///
+///     #[default(each = |i| i as u8)]
+///     ramp: [u8; 256],
+/// }
+///
+/// fn main() {
+///     let buffers = Buffers::<64>::default();
+///     assert_eq!(buffers.samples, [0.0; 64]);
+///     assert_eq!(buffers.ramp[200], 200);
+/// }
+/// ```
+///
+/// ### 7. Types without a `Default` implementation
+///
+/// Some types don't implement `Default`, but have a sensible default value. With `#[default(auto)]` on a field, or the `auto` option in the top attribute for all the fields without a default value, these types get this value :
+///
+/// | Type                                      | Default value               |
+/// |-------------------------------------------|-----------------------------|
+/// | `*const T`, `*mut T`                      | a null pointer              |
+/// | `NonNull<T>`                              | `NonNull::dangling()`       |
+/// | `NonZeroU32`, `NonZero<T>`, ...           | `MIN`                       |
+/// | `&str`                                    | `""`                        |
+/// | `&[T]`, `&mut [T]`                        | an empty slice              |
+/// | `Option<T>` (like `Option<&T>`)           | `None`                      |
+///
+/// The other types keep their `Default` value, and the function pointers, which have no sensible default value, are reported as an error. Like the arrays and the tuples, the types are detected syntactically.
+///
+/// ```rust
+/// use better_default::Default;
+/// use std::num::NonZeroU32;
+///
+/// #[derive(Default)]
+/// #[default(auto)]
+/// struct Handle {
+///     id: NonZeroU32,
+///     name: &'static str,
+///     raw: *const u8,
+/// }
+///
+/// fn main() {
+///     let handle = Handle::default();
+///     assert_eq!(handle.id.get(), 1);
+///     assert_eq!(handle.name, "");
+///     assert!(handle.raw.is_null());
+/// }
+/// ```
+///
+/// ### 8. Zeroed structs
+///
+/// For large structs, like the ones shared with C code, the `zeroed` option in the top attribute builds the struct from zeroed memory (`core::mem::zeroed()`), then assigns the fields with a default value :
+///
+/// ```rust
+/// use better_default::Default;
+/// use std::ffi::{c_char, c_int};
+///
+/// #[derive(Default)]
+/// #[default(zeroed, version: 2)]
+/// #[repr(C)]
+/// struct Header {
+///     version: c_int,
+///     name: *const c_char,
+///     reserved: [u8; 256],
+/// }
+///
+/// #[derive(Default)]
+/// #[default(zeroed)]
+/// #[repr(C)]
+/// struct Packet {
+///     header: Header,
+///     previous: Header,
+///     #[default(1500)]
+///     size: u32,
+/// }
+///
+/// fn main() {
+///     let packet = Packet::default();
+///     assert_eq!(packet.header.version, 0);
+///     assert_eq!(packet.size, 1500);
+///
+///     let header = Header::default();
+///     assert_eq!(header.version, 2);
+///     assert!(header.name.is_null());
+/// }
+/// ```
+///
+/// The fields without a default value stay zeroed, including the nested structs, whose own default values aren't used.
+///
+/// Since the zeroed memory must be a valid value of every field, `zeroed` is only accepted when the types of all the fields are known to be valid with all their bytes at zero :
+///  - the integers, the floats, `bool`, `char` and their FFI aliases (`c_int`, ...), `PhantomData` and `MaybeUninit`
+///  - the raw pointers
+///  - the `Option`s of references, function pointers, `Box`, `NonNull` and the non-zero integers
+///  - the arrays and tuples of those types
+///  - the structs which use `zeroed` too
+///
+/// The other types, like the references or the non-zero integers, are reported as errors.
+///
+/// ### 9. Building large values in place
+///
+/// Building a large value with `Box::new(Type::default())` builds it on the stack first, which can overflow it. With the `in_place` option in the top attribute of a struct, two more associated functions are generated :
+///  - `default_boxed() -> Box<Self>`, which allocates the value on the heap, then writes its default value in it.
+///  - `unsafe fn default_in_place(slot: *mut Self)`, which writes the default value at `slot`, field by field.
+///
+/// The arrays without a default value are written element by element, and the fields marked with `#[default(in_place)]` are written with the `default_in_place` function of their own type, so that the value is never built on the stack :
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default)]
+/// #[default(in_place)]
+/// struct Buffers {
+///     samples: [f32; 1 << 20],
+/// }
+///
+/// #[derive(Default)]
+/// #[default(in_place)]
+/// struct State {
+///     #[default(in_place)]
+///     buffers: Buffers,
+///
+///     #[default(1)]
+///     version: u32,
+/// }
+///
+/// fn main() {
+///     let state = State::default_boxed();
+///     assert_eq!(state.buffers.samples[1000], 0.0);
+///     assert_eq!(state.version, 1);
+/// }
+/// ```
+///
+/// > `default_boxed` uses the `Box` of the std library, or, without the `std` feature, the one of the `alloc` crate, which must be declared with `extern crate alloc;`.
+///
+/// ### 10. Cached default values
+///
+/// When the default value is expensive to compute and often needed, the `cached` option in the top attribute computes it once, the first time it's needed, and stores it in a static. `default()` then clones it, and the generated `default_ref() -> &'static Self` gives a reference to it :
+///
+/// ```rust
+/// use better_default::Default;
+/// use std::collections::HashMap;
+///
+/// #[derive(Default, Clone)]
+/// #[default(cached)]
+/// struct Config {
+///     #[default((0..1000).map(|i| (i.to_string(), i)).collect())]
+///     table: HashMap<String, u32>,
+/// }
 ///
-/// ```rust, ignore
+/// fn main() {
+///     let config = Config::default();
+///     assert_eq!(config.table["42"], 42);
+///
+///     let shared: &'static Config = Config::default_ref();
+///     assert_eq!(shared.table.len(), 1000);
+/// }
+/// ```
+///
+/// Since the value is shared between threads and cloned, the type must implement `Clone`, `Send` and `Sync`, and it can't be generic.
+///
+/// > The static uses `std::sync::OnceLock`, so this option requires the `std` feature (enabled by default).
+///
+/// ### 11. Modifiers
+///
+/// A default value can be preceded by modifiers, which wrap the expression following them :
+///
+/// | Modifier         | Generated expression      |
+/// |------------------|---------------------------|
+/// | `into <expr>`    | `Into::into(<expr>)`      |
+/// | `some <expr>`    | `Some(<expr>)`            |
+/// | `boxed <expr>`   | `Box::new(<expr>)`        |
+/// | `arc <expr>`     | `Arc::new(<expr>)`        |
+/// | `rc <expr>`      | `Rc::new(<expr>)`         |
+///
+/// The modifiers can be chained, like `some into "text"`, which gives `Some(Into::into("text"))`. The `into_all` option in the top attribute converts all the default values with `Into`, like the `into` modifier.
+///
+/// ```rust
 /// use better_default::Default;
 ///
+/// trait Handler {}
+/// struct Logger;
+/// impl Handler for Logger {}
+///
 /// #[derive(Default)]
-/// enum Enum {
-///     #[default]
-///     Variant {
-///         ...
-///     },
-///     ...
+/// #[default(host: into "localhost")]
+/// struct Server {
+///     host: String,
+///
+///     #[default(some 8080)]
+///     port: Option<u16>,
+///
+///     #[default(boxed Logger)]
+///     handler: Box<dyn Handler>,
+/// }
+///
+/// fn main() {
+///     let server = Server::default();
+///     assert_eq!(server.host, "localhost");
+///     assert_eq!(server.port, Some(8080));
 /// }
 /// ```
 ///
-/// ### 1. Overriding the default values
+/// A modifier must be followed by a literal, a path, a block or a collection literal (see below), so that `some(5)` stays a call to a function named `some`, and `some - 1` a subtraction. The other values, like the negative numbers, are written without the modifier, like `Some(-1)`.
 ///
-/// There a two ways of overriding the default values : using the per-field attributes or the top default attributes.
+/// > Without the `std` feature, the `boxed`, `arc` and `rc` modifiers use the `alloc` crate, which must be declared with `extern crate alloc;`.
 ///
-/// #### Per-Field attributes
+/// ### 12. Collection literals
 ///
-/// The per-field attributes are simply attributes you put atop of the fields for which you want to override the default values.
+/// The collections can be written as literals :
+///  - `[<element>, ...]`, like `[1, 2, 3]` for a `Vec<u32>`, or `["a", "b"]` for a `HashSet<String>`.
+///  - `{<key>: <value>, ...}` for the maps, like `{"a": 1, "b": 2}` for a `HashMap<String, u32>`.
 ///
-/// The syntax is the following :
-/// ```rust, ignore
-/// #[default( <expression> )]
-/// <field_ident>: <field_type>
+/// They generate `IntoIterator::into_iter([...]).collect()`, with the elements, the keys and the values converted with `Into` (except for the number literals, which take the type of the collection elements directly). The maps can't have the same key twice.
+///
+/// ```rust
+/// use better_default::Default;
+/// use std::collections::{BTreeMap, HashMap};
+///
+/// #[derive(Default)]
+/// #[default(ports: [80, 443])]
+/// struct Config {
+///     ports: Vec<u16>,
+///
+///     #[default(["localhost", "127.0.0.1"])]
+///     hosts: Vec<String>,
+///
+///     #[default({"soft": 10, "hard": 20})]
+///     limits: HashMap<String, u32>,
+///
+///     #[default({1: "admin", 2: "user"})]
+///     roles: BTreeMap<u8, String>,
+///
+///     #[default([1, 2])]
+///     array: [u8; 2], // the arrays stay arrays
+/// }
+///
+/// fn main() {
+///     let config = Config::default();
+///     assert_eq!(config.ports, [80, 443]);
+///     assert_eq!(config.limits["hard"], 20);
+///     assert_eq!(config.roles[&1], "admin");
+/// }
 /// ```
 ///
-/// You can put anything you want in the `expression` bloc, **as long as it can be correctly parse by [syn::Expr](https://docs.rs/syn/latest/syn/enum.Expr.html).**
+/// An array literal is collected into any type implementing `FromIterator`, like `Vec`, `HashSet` or `Box<[T]>`. It stays an array for the array types, even behind an alias, and when it follows the `into` modifier. The literals nested in the elements aren't collected, they are converted with `Into` like the other elements.
+///
+/// ### 13. Parsed default values
+///
+/// The default value can be written as a string with `parse = "<string>"`, which is parsed into the field type :
 ///
-/// Here is an example of this approach in action :
 /// ```rust
 /// use better_default::Default;
+/// use std::net::{IpAddr, SocketAddr};
+/// use std::path::PathBuf;
 ///
-/// #[derive(Default, Debug)]
-/// enum Enum {
-///     #[default] // mark the variant as default (this is mandatory)
-///     Variant1 {
-///         #[default(1)] // set the default value of `first` to 1
-///         first: u32,
+/// #[derive(Default)]
+/// #[default(ip: parse = "::1")]
+/// struct Server {
+///     #[default(parse = "127.0.0.1:8080")]
+///     addr: SocketAddr,
 ///
-///         // keep the default value for `second`
-///         second: String,
-///     },
+///     ip: IpAddr,
 ///
-///     Variant2,
+///     #[default(parse = "/var/log")]
+///     logs: PathBuf,
+/// }
 ///
-///     Variant3,
+/// fn main() {
+///     let server = Server::default();
+///     assert_eq!(server.addr.port(), 8080);
+/// }
+/// ```
+///
+/// For the integers, the floats, `bool`, `char`, and the addresses of `core::net` (`IpAddr`, `Ipv4Addr`, `Ipv6Addr`, `SocketAddr`, `SocketAddrV4` and `SocketAddrV6`), the string is parsed at compile time, an invalid string being reported as an error, and the value is built directly, without parsing it at runtime. The other types are parsed at runtime with `FromStr`, panicking with the name of the field if the string is invalid.
+///
+/// ### 14. Durations and byte sizes
+///
+/// The durations and the byte sizes can be written as strings, which are converted at compile time :
+///  - `duration = "<duration>"` gives a `Duration`, with the units `ms`, `s`, `m`, `h` and `d`, like `"30s"`, `"1.5ms"` or `"1h 30m"`.
+///  - `bytes = "<size>"` gives an integer, with the units `B`, `KB`, `KiB`, `MB`, `MiB`, `GB` and `GiB`, like `"64MiB"`. When the field type is an integer type, the size is checked to fit in it.
+///
+/// ```rust
+/// use better_default::Default;
+/// use std::time::Duration;
+///
+/// #[derive(Default)]
+/// #[default(idle: duration = "1h 30m")]
+/// struct Limits {
+///     #[default(duration = "30s")]
+///     timeout: Duration,
+///
+///     idle: Duration,
+///
+///     #[default(some duration = "250ms")]
+///     grace: Option<Duration>,
+///
+///     #[default(bytes = "64MiB")]
+///     max_body: u64,
 /// }
 ///
 /// fn main() {
-///     let default = Enum::default();
+///     let limits = Limits::default();
+///     assert_eq!(limits.timeout, Duration::from_secs(30));
+///     assert_eq!(limits.max_body, 64 * 1024 * 1024);
+/// }
+/// ```
 ///
-///     // should print "Variant1 { first: 1, second: "" }"
-///     println!("{:?}", default);
+/// Like the expressions, the strings of `parse`, `duration` and `bytes` can follow modifiers, like `some`.
+///
+/// ### 15. Validation
+///
+/// A field value can be followed by `range = <range>`, checking that it's in the range. When the value and the bounds are literals which can be compared exactly, the check is done at compile time, otherwise the value is checked with `debug_assert!`.
+///
+/// The `validate = <function>` option of the top attribute checks the whole value once it's built, with a function taking `&Self` and returning a `Result<(), E>`, where `E` implements `Display`. In debug builds, an error panics with a message naming the type.
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default)]
+/// #[default(validate = Self::check)]
+/// struct Pool {
+///     #[default(8080, range = 1..=65535)]
+///     port: u32,
+///
+///     #[default(2, range = 1..)]
+///     min_workers: u32,
+///
+///     #[default(8)]
+///     max_workers: u32,
+/// }
+///
+/// impl Pool {
+///     fn check(&self) -> Result<(), String> {
+///         if self.min_workers > self.max_workers {
+///             return Err("`min_workers` is greater than `max_workers`".to_string());
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() {
+///     let pool = Pool::default();
+///     assert_eq!(pool.port, 8080);
 /// }
 /// ```
 ///
-/// #### Top Default attributes
+/// `range` can only follow an expression, in a field attribute. The validation also runs on the profiles, and at the end of `default_in_place`.
 ///
-/// Instead of placing an attribute on all the fields of a struct / enum variant, you place only on attribute atop of it, containing all the default values overrides.
+/// ### 16. Recursion checks
 ///
-/// The syntax of the top attributes is the following :
-/// ```rust, ignore
+/// A default value calling the `Default` implementation it's part of recurses infinitely, and overflows the stack at runtime. The derive reports those default values :
+///  - the calls to `Self::default()`, `<Self as Default>::default()` or `<type>::default()` in a default value.
+///  - the calls to `Default::default()` building a `Self`, like `boxed Default::default()` on a `Box<Self>` field.
+///  - the fields without a default value whose type holds the type itself, like `Box<Self>` or `(u32, Rc<Self>)`. The types like `Option<Box<Self>>` or `Vec<Self>` are fine, since their default value is empty.
+///
+/// ```rust, compile_fail
 /// use better_default::Default;
 ///
 /// #[derive(Default)]
-/// #[default((<field_id>: <expression>),*)]
-/// struct Struct {
-///     ...
-/// } // the struct can have unnamed fields
+/// enum Expr {
+///     #[default]
+///     Negate(Box<Expr>), // error : `Box<Expr>` builds an `Expr` again
+///     Literal(i32),
+/// }
+/// ```
+///
+/// The checks are syntactic, so they can be disabled with `allow(recursion)` in the top attribute, for example when the recursion stops at runtime.
+///
+/// ```rust
+/// use better_default::Default;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static LINKS: AtomicUsize = AtomicUsize::new(0);
 ///
 /// #[derive(Default)]
-/// enum Enum {
-///     #[default((<field_id>: <expression>),*)]
-///     Variant { ... } // the variant can have unnamed fields
+/// #[default(allow(recursion))]
+/// struct Chain {
+///     #[default(if LINKS.fetch_add(1, Ordering::Relaxed) < 2 { Some(Box::new(Self::default())) } else { None })]
+///     next: Option<Box<Chain>>,
+/// }
+///
+/// fn main() {
+///     let chain = Chain::default();
+///     assert!(chain.next.unwrap().next.unwrap().next.is_none());
 /// }
 /// ```
 ///
-/// `field_id` here can means two things : if you deal with named fields, you should put the field ident here. If you deal with unnamed fields, then **you should put the position of the field** *(0 for the first, 1 for the second, etc.)*.
+/// ### 17. Redundant default values
 ///
-/// Again, you can put anything you want in the `expression` bloc, **as long as it can be correctly parse by [syn::Expr](https://docs.rs/syn/latest/syn/enum.Expr.html).**
+/// The derive warns about the default values which change nothing, each warning being a lint which can be disabled with `allow(<lint>, ...)` in the top attribute :
+///  - `redundant` : the value is the default value of the field type, like `0` for an integer, `false`, `None`, `""`, `String::new()`, `vec![]`, `[]` for a collection or `Default::default()`.
+///  - `redundant_profile` : the value of a profile is the same as the unqualified value, which the profile already falls back to.
 ///
-/// Here are two examples, one covering unnamed fields and one covering named ones.
 /// ```rust
 /// use better_default::Default;
 ///
-/// #[derive(Default, Debug)]
-/// enum Enum {
-///     // mark the variant as default, and also specifies the default values :
-///     //      - the default value of the first field (which is at index 0) is set to 2
-///     //      - the second field (which is at index 1) will have it's default value set to "Hello world!"
-///     #[default(0: 2, 1: "Hello world!".to_string())]
-///     Variant1(u32, String),
+/// #[derive(Default)]
+/// struct Config {
+///     #[default(0)] // warning : `0` is already the default value of `u16`
+///     port: u16,
 ///
-///     Variant2,
+///     #[default(3)]
+///     #[default(profile = "prod", 3)] // warning : the profile already falls back to `3`
+///     retries: u32,
+/// }
 ///
-///     Variant3,
+/// #[derive(Default)]
+/// #[default(allow(redundant))]
+/// struct Quiet {
+///     #[default(0)] // no warning
+///     port: u16,
+/// }
+/// fn main() {}
+/// ```
+///
+/// Stable proc macros can't emit warnings, so the warnings are the deprecation warnings of constants named after the lints, which point at the default values. They can also be disabled with `#[allow(deprecated)]`, but this disables the deprecation warnings of the whole module.
+///
+/// ### 18. Variant constructors
+///
+/// The variants of an enum with default values on their fields, other than the default variant, get a `default_<variant>()` constructor, the name of the variant being converted to snake case, which builds them with these values. With the `variants` option in the top attribute, every variant gets a constructor, and `variant_defaults()` returns all of them in declaration order, for example to fill a dropdown. `Default` still returns the variant marked as default.
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug, PartialEq)]
+/// #[default(variants)]
+/// enum Shape {
+///     #[default]
+///     Circle {
+///         #[default(1.0)]
+///         radius: f32,
+///     },
+///
+///     Rectangle {
+///         #[default(2.0)]
+///         width: f32,
+///         #[default(3.0)]
+///         height: f32,
+///     },
+///
+///     HTTPIcon,
 /// }
 ///
 /// fn main() {
-///     let default = Enum::default();
+///     assert_eq!(Shape::default(), Shape::default_circle());
+///     assert_eq!(Shape::default_rectangle(), Shape::Rectangle { width: 2.0, height: 3.0 });
+///     assert_eq!(Shape::default_http_icon(), Shape::HTTPIcon);
+///     assert_eq!(Shape::variant_defaults().len(), 3);
+/// }
+/// ```
 ///
-///     // should print "Variant1(2, "Hello world!")"
-///     println!("{:?}", default);
+/// The options of the top attribute of the enum, like `into_all` or `validate`, apply to every variant, but the profiles can only be used in the default variant. The constructor of a variant with a field of a generic type only requires this type to implement `Default`. Without the `variants` option, the variants without default values are left as they are, so like with the std derive, their fields don't need to implement `Default`.
+///
+/// Since a top attribute on a variant marks it as a default variant, the values of the other variants can only be given in the attributes of their fields. A variant whose constructor has the name of another generated function, like `Ref` with the `cached` option, which generates `default_ref`, is reported.
+///
+/// ### 19. Conditional default variants
+///
+/// Several variants can be marked as default with a condition, placed before their default values like a profile :
+///  - `#[default(cfg(<predicate>))]` selects the variant at compile time, in the configurations matching the predicate.
+///  - `#[default(when = <expression>)]` selects the variant at runtime, when the expression is `true`.
+///
+/// The conditions are checked in declaration order, the first matching variant being the default one, and the variant marked with an unconditional `#[default]` is the fallback when none of them holds.
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug, PartialEq)]
+/// enum Backend {
+///     #[default(cfg(target_os = "linux"))]
+///     Epoll,
+///
+///     #[default(cfg(windows))]
+///     Iocp,
+///
+///     #[default(when = std::env::var_os("FORCE_SELECT").is_some())]
+///     Select,
+///
+///     #[default]
+///     Poll,
+/// }
+///
+/// fn main() {
+///     let backend = Backend::default();
+///
+///     if cfg!(target_os = "linux") {
+///         assert_eq!(backend, Backend::Epoll);
+///     }
 /// }
 /// ```
 ///
+/// The derive generates a `fn default()` per `cfg` condition, guarded by this condition. Without an unconditional variant, the configurations matching none of the `cfg` conditions fail to compile, and the `when` conditions always need one. The profiles can only be used in the unconditional variant, and the conditional default variants are only supported by the `Default` derive.
+///
+/// ### 20. Values per configuration
+///
+/// A default value can be preceded by values for some configurations, each one keyed by a `cfg` predicate : `cfg(<predicate>) = <value>, ..., <fallback>`. The first value whose predicate holds is used, and the fallback value, which is mandatory, is used in the other configurations. This works in the field attributes and in the top attributes.
+///
 /// ```rust
 /// use better_default::Default;
 ///
 /// #[derive(Default, Debug)]
-/// #[default(field1: 1, field2: "Hello world!".to_string())]
-/// struct Struct {
-///     field1: u32,
-///     field2: String
+/// #[default(directory: cfg(windows) = "C:\\logs", "/var/log")]
+/// struct Logging {
+///     #[default(cfg(debug_assertions) = 3, 1)]
+///     verbosity: u8,
+///
+///     #[default(cfg(feature = "compression") = Some(Codec::zstd()), None)]
+///     codec: Option<Codec>,
+///
+///     directory: &'static str,
+/// }
+///
+/// #[derive(Debug)]
+/// struct Codec;
+///
+/// impl Codec {
+///     #[cfg(feature = "compression")]
+///     fn zstd() -> Self {
+///         Codec
+///     }
 /// }
 ///
 /// fn main() {
-///     let default = Struct::default();
-///     println!("{:?}", default) // should print "Struct { field1: 1, field2: "Hello world!" }"
+///     let logging = Logging::default();
+///     assert_eq!(logging.verbosity, if cfg!(debug_assertions) { 3 } else { 1 });
 /// }
 /// ```
 ///
-/// One last note : **these two approaches can be combined,
-/// which means you can have a top attribute containing some
-/// default values while some of the fields have their
-/// own attribute.**
+/// The values are put in blocks gated by `#[cfg]` attributes, so only the value of the current configuration is type-checked, and the values of the other configurations can use items which don't exist in this one. A `range` applies to the values of every configuration.
+///
+/// ### 21. Fields behind `cfg`
+///
+/// The `cfg` attributes of the fields are put on the code generated for them. A field removed by its `cfg` doesn't reach the derive at all, so its default value in a top attribute must be guarded by the same `cfg`, to not be reported as an unknown field :
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug)]
+/// #[default(#[cfg(feature = "tls")] tls_cert: "cert.pem".to_string(), port: 443)]
+/// struct Server {
+///     #[cfg(feature = "tls")]
+///     tls_cert: String,
+///
+///     port: u16,
+/// }
+///
+/// fn main() {
+///     assert_eq!(Server::default().port, 443);
+/// }
+/// ```
+///
+/// The guards are checked by the compiler : the field is reported as unknown only in the configurations where its guards hold, and a guarded value of a field which exists while its guards don't hold is an error too.
+///
+/// ### 22. Versioned default values
+///
+/// A field can change its default value from a version on, while keeping the older values, for example to load the configurations written with an older version. The versioned values take the version before the value, and are declared from the oldest to the newest :
+///
+/// ```rust
+/// use better_default::Default;
+///
+/// #[derive(Default, Debug, PartialEq)]
+/// struct Config {
+///     #[default(50)]
+///     #[default(since = 3, 100)]
+///     #[default(since = 5, 200)]
+///     timeout: u32,
+///
+///     #[default(since = 2, true)]
+///     compress: bool,
+/// }
+///
+/// fn main() {
+///     assert_eq!(Config::DEFAULTS_VERSION, 5);
+///     assert_eq!(Config::default(), Config { timeout: 200, compress: true });
+///
+///     assert_eq!(Config::default_for_version(0), Config { timeout: 50, compress: false });
+///     assert_eq!(Config::default_for_version(4), Config { timeout: 100, compress: true });
+/// }
+/// ```
+///
+/// The derive generates `default_for_version(version: u32)`, which gives each field its newest value whose version is at most `version`, the value without `since` being the value of the version 0, and `DEFAULTS_VERSION`, the newest version of the type. `Default` and the profiles use the newest values. The versions of a field must be increasing, and the versioned values can only be used in the default variant of an enum without conditional default variants.
 ///
 /// ## Examples
 ///
@@ -720,6 +2205,41 @@ mod constants;
 ///     println!("{:?}", default) // should print "Struct { field1: 1, field2: "Hello world!" }"
 /// }
 /// ```
+///
+/// ## Optional features
+///
+/// ### `std`
+///
+/// Enabled by default. Lets the generated code use the std library for the options which need it, like `trace_panics` and `cached`. Disable it in `no_std` crates.
+///
+/// ### `clap`
+///
+/// When a struct derives both `clap::Parser` and `Default`, the `clap_defaults` attribute gives each argument its `better_default` value as `clap` default value, so the `--help` output and `Default` agree, unless the `#[arg(...)]` attribute of the field already sets a default value.
+///
+/// > **The attribute must be placed above the `derive` attribute.**
+///
+/// ```rust, ignore
+/// use better_default::Default;
+/// use clap::Parser;
+///
+/// #[better_default::clap_defaults]
+/// #[derive(Parser, Default)]
+/// struct Cli {
+///     #[arg(long)]
+///     #[default("localhost".to_string())] // also becomes `default_value_t`
+///     host: String,
+///
+///     #[arg(long)]
+///     port: u16, // gets `default_value_t = <u16 as Default>::default()`
+///
+///     #[arg(long, default_value_t = 3)] // already has a default value, left untouched
+///     retries: u32,
+/// }
+/// ```
+///
+/// `Vec` fields get `default_values_t` instead, and `PathBuf` and `OsString` fields `default_value_os_t`. Since the default parser of `PathBuf` rejects the empty path, a `PathBuf` field without a default value nor a `value_parser` gets a parser accepting it.
+///
+/// An `Option` field is `None` when its argument is missing, so only a default value written `Some(<value>)` is given to clap, other values being reported. The field types clap can't display are reported too, and the `clap::ValueEnum` types need `#[arg(value_enum)]` to be displayed. `#[command(...)]` fields are left untouched.
 // #[include_doc("../README.md", end("## Contributing"))]
 #[proc_macro_derive(Default, attributes(default))]
 pub fn better_default(input: TokenStream) -> TokenStream {
@@ -727,3 +2247,56 @@ pub fn better_default(input: TokenStream) -> TokenStream {
 
     derive::derive(&input).into()
 }
+
+/// Generates a `default_with` associated function, building the default value with a context.
+///
+/// The context parameter is declared with the `#[default_with(<ident>: <type>)]` attribute, and the default values
+/// can use it with the `<ident> => <expression>` syntax, in the per-field attributes and in the top attributes.
+/// The fields without such a value keep their usual default value, and the fields marked with `#[default_with]`
+/// are built with the `default_with` function of their own type, receiving the same context.
+///
+/// `Default` ignores the values using the context, and uses the default value of the field type instead.
+///
+/// > Since procedural macro crates can't export traits, `default_with` is an associated function of the type, not the method of a trait.
+#[proc_macro_derive(DefaultWith, attributes(default, default_with))]
+pub fn better_default_with(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    default_with::derive(&input).into()
+}
+
+/// Generates a `try_default` associated function, for the types whose default values can fail to be computed.
+///
+/// The error type is declared in the top attribute with `#[default(error = <type>)]`, and the default values can
+/// propagate errors into it with `?`, `try_default` returning the error of the first field which failed.
+///
+/// The top attribute also accepts these options :
+///  - `field_error = <expression>` : a function `fn(&'static str, <error>) -> <error>`, called with the name of the
+///    failing field and its error, to wrap the error with the name of the field.
+///  - `impl_default` : also implements `Default`, panicking with the name of the failing field and its error, which must implement `Debug`.
+///
+/// > Since procedural macro crates can't export traits, `try_default` is an associated function of the type, not the method of a trait.
+#[proc_macro_derive(TryDefault, attributes(default))]
+pub fn better_try_default(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    try_default::derive(&input).into()
+}
+
+/// Gives the fields of a `clap` parser their `better_default` value as `clap` default value.
+///
+/// Each field gets an `#[arg(default_value_t = <default value>)]` attribute (`default_values_t` for `Vec`,
+/// `default_value_os_t` for `PathBuf` and `OsString`), so the `--help` output and the derived `Default` agree.
+/// An `Option` field only gets the value of a default value written `Some(<value>)`, since clap gives `None` to a missing argument.
+/// `#[command(...)]` fields and fields which already have a default value in their `#[arg(...)]` attribute are left untouched.
+///
+/// **This attribute must be placed above the `derive` attribute**, otherwise `clap` won't see the added attributes.
+///
+/// Only available with the `clap` feature.
+#[cfg(feature = "clap")]
+#[proc_macro_attribute]
+pub fn clap_defaults(args: TokenStream, input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    clap::expand(&args.into(), input).into()
+}
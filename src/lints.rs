@@ -0,0 +1,128 @@
+use quote::{quote_spanned, ToTokens};
+use syn::{Expr, Ident, Lit, Type};
+
+use crate::{constants, types, units, Span2, TokenStream2};
+
+/// The types whose `new` function builds their default value, an empty collection.
+const EMPTY_NEW_TYPES: &[&str] = &[
+    "String",
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "BinaryHeap",
+    "HashMap",
+    "HashSet",
+    "BTreeMap",
+    "BTreeSet",
+];
+
+/// Emits a compiler warning at `span`, since the proc macros can't emit warnings on stable.
+///
+/// The warning is the deprecation of a constant named after the lint, used at `span`.
+pub fn warning(lint: &str, span: Span2, message: &str) -> TokenStream2 {
+    let ident = Ident::new(lint, span);
+    let note = format!(
+        "{} (`#[{}({}({}))]` disables this warning)",
+        message,
+        constants::DEFAULT_IDENT,
+        constants::ALLOW_OPTION,
+        lint
+    );
+
+    quote_spanned! { span=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_upper_case_globals)]
+            const #ident: () = ();
+
+            #ident
+        };
+    }
+}
+
+/// Whether `expr` is the path `name`, like `None` or `Option::None` for `"None"`.
+fn is_path_to(expr: &Expr, name: &str) -> bool {
+    matches!(expr, Expr::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == name))
+}
+
+/// Whether `expr` is `""`.
+fn is_empty_str(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(lit) if matches!(&lit.lit, Lit::Str(s) if s.value().is_empty()))
+}
+
+/// Whether `expr` is the default value of the type `ty`, like `0` for an integer, `None` or `String::new()`.
+///
+/// The check is syntactic, so it only recognizes the usual ways to write those values.
+pub fn is_redundant(expr: &Expr, ty: &Type) -> bool {
+    if let Type::Reference(reference) = types::strip(ty) {
+        let is_str = matches!(&*reference.elem, Type::Path(path) if path.path.is_ident("str"));
+        return is_str && is_empty_str(expr);
+    }
+
+    let Some(segment) = types::last_segment(ty) else {
+        return false;
+    };
+    let name = segment.ident.to_string();
+    let name = name.as_str();
+
+    match expr {
+        Expr::Paren(paren) => is_redundant(&paren.expr, ty),
+        Expr::Group(group) => is_redundant(&group.expr, ty),
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => {
+                units::INTEGER_TYPES.iter().any(|(integer, _)| *integer == name)
+                    && int.base10_parse::<u128>().is_ok_and(|value| value == 0)
+            }
+            Lit::Float(float) => {
+                (name == "f32" || name == "f64")
+                    && float.base10_parse::<f64>().is_ok_and(|value| value == 0.0)
+            }
+            Lit::Bool(bool) => name == "bool" && !bool.value,
+            Lit::Char(char) => name == "char" && char.value() == '\0',
+            _ => false,
+        },
+        Expr::Path(_) => name == "Option" && is_path_to(expr, "None"),
+        Expr::Call(call) if call.args.is_empty() => {
+            let Expr::Path(func) = &*call.func else {
+                return false;
+            };
+
+            // `<T>::default()` or `<T as Default>::default()`
+            if func.qself.is_some() {
+                return is_path_to(&call.func, "default");
+            }
+
+            let segments = &func.path.segments;
+            let Some(function) = segments.last() else {
+                return false;
+            };
+            let receiver = segments.iter().nth_back(1).map(|s| s.ident.to_string());
+
+            match (function.ident.to_string().as_str(), receiver.as_deref()) {
+                // `Default::default()` or `<type>::default()`
+                ("default", Some(receiver)) => receiver == "Default" || receiver == name,
+                ("new", Some(receiver)) => receiver == name && EMPTY_NEW_TYPES.contains(&name),
+                _ => false,
+            }
+        }
+        // `"".to_string()`, `"".to_owned()` or `"".into()`
+        Expr::MethodCall(call) => {
+            name == "String"
+                && call.args.is_empty()
+                && ["to_string", "to_owned", "into"].iter().any(|m| call.method == m)
+                && is_empty_str(&call.receiver)
+        }
+        // `vec![]`
+        Expr::Macro(mac) => {
+            name == "Vec" && mac.mac.path.is_ident("vec") && mac.mac.tokens.is_empty()
+        }
+        // `[]`, collected into an empty collection
+        Expr::Array(array) => array.elems.is_empty() && EMPTY_NEW_TYPES.contains(&name),
+        _ => false,
+    }
+}
+
+/// Whether the value of a profile is the same as the unqualified value, which the profile already falls back to.
+pub fn is_same_value(a: &Expr, b: &Expr) -> bool {
+    a.to_token_stream().to_string() == b.to_token_stream().to_string()
+}
@@ -0,0 +1,134 @@
+use syn::{parse::ParseStream, punctuated::Punctuated, Expr, Ident, Token, Type};
+
+use crate::constants;
+
+/// The options of the top default attribute of a struct or an enum, like `#[default(error = MyError)]`.
+#[derive(Clone, Default)]
+pub struct ContainerOptions {
+    /// `error = <type>` : the error type of `try_default`.
+    pub error: Option<Type>,
+    /// `field_error = <expression>` : wraps the error of a field with the name of the field.
+    pub field_error: Option<Expr>,
+    /// `impl_default` : also implements `Default` in the `TryDefault` derive.
+    pub impl_default: Option<Ident>,
+    /// `trace_panics` : reports the field being computed when a default value panics.
+    pub trace_panics: Option<Ident>,
+    /// `auto` : gives their `auto` default value to the fields without a default value.
+    pub auto: Option<Ident>,
+    /// `zeroed` : builds the struct from zeroed memory, then assigns the overridden fields.
+    pub zeroed: Option<Ident>,
+    /// `in_place` : generates `default_in_place` and `default_boxed`, writing the fields in place.
+    pub in_place: Option<Ident>,
+    /// `cached` : computes the default value once, then clones it.
+    pub cached: Option<Ident>,
+    /// `into_all` : converts all the default values with `Into`, like the `into` modifier.
+    pub into_all: Option<Ident>,
+    /// `validate = <expression>` : checks the built value with a `fn(&Self) -> Result<(), E>`, in debug builds.
+    pub validate: Option<Expr>,
+    /// `variants` : generates the constructor of every variant of an enum, and `variant_defaults`.
+    pub variants: Option<Ident>,
+    /// `allow(<lint>, ...)` : disables some checks of the derive, like `recursion`.
+    pub allow: Vec<Ident>,
+    /// The idents of all the parsed options, in declaration order.
+    pub idents: Vec<Ident>,
+}
+
+fn set_once<T>(option: &mut Option<T>, ident: &Ident, value: T) -> syn::Result<()> {
+    if option.is_some() {
+        return Err(error!(ident.span(), "this option is already declared."));
+    }
+
+    *option = Some(value);
+    Ok(())
+}
+
+impl ContainerOptions {
+    pub fn parse_option(&mut self, input: ParseStream) -> syn::Result<()> {
+        let ident: Ident = input.parse()?;
+
+        match ident.to_string().as_str() {
+            constants::ERROR_OPTION => {
+                input.parse::<Token![=]>()?;
+                set_once(&mut self.error, &ident, input.parse()?)?;
+            }
+            constants::FIELD_ERROR_OPTION => {
+                input.parse::<Token![=]>()?;
+                set_once(&mut self.field_error, &ident, input.parse()?)?;
+            }
+            constants::IMPL_DEFAULT_OPTION => {
+                set_once(&mut self.impl_default, &ident, ident.clone())?;
+            }
+            constants::TRACE_PANICS_OPTION => {
+                set_once(&mut self.trace_panics, &ident, ident.clone())?;
+            }
+            constants::AUTO_OPTION => {
+                set_once(&mut self.auto, &ident, ident.clone())?;
+            }
+            constants::ZEROED_OPTION => {
+                set_once(&mut self.zeroed, &ident, ident.clone())?;
+            }
+            constants::IN_PLACE_OPTION => {
+                set_once(&mut self.in_place, &ident, ident.clone())?;
+            }
+            constants::CACHED_OPTION => {
+                set_once(&mut self.cached, &ident, ident.clone())?;
+            }
+            constants::INTO_ALL_OPTION => {
+                set_once(&mut self.into_all, &ident, ident.clone())?;
+            }
+            constants::VALIDATE_OPTION => {
+                input.parse::<Token![=]>()?;
+                set_once(&mut self.validate, &ident, input.parse()?)?;
+            }
+            constants::VARIANTS_OPTION => {
+                set_once(&mut self.variants, &ident, ident.clone())?;
+            }
+            constants::ALLOW_OPTION => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                for lint in Punctuated::<Ident, Token![,]>::parse_terminated(&content)? {
+                    if !constants::LINTS.contains(&lint.to_string().as_str()) {
+                        return Err(error!(
+                            lint.span(),
+                            "unknown lint `{}`, the lints are {}.",
+                            lint,
+                            constants::LINTS
+                                .iter()
+                                .map(|lint| format!("`{}`", lint))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ));
+                    }
+
+                    if self.allow.contains(&lint) {
+                        return Err(error!(lint.span(), "this lint is already allowed."));
+                    }
+
+                    self.allow.push(lint);
+                }
+            }
+            _ => {
+                return Err(error!(
+                    ident.span(),
+                    "unknown option `{}`, if it's a field, its default value must follow a colon (`{}: <expression>`).",
+                    ident,
+                    ident
+                ))
+            }
+        }
+
+        self.idents.push(ident);
+        Ok(())
+    }
+
+    /// Whether the lint `name` is disabled with the `allow` option.
+    pub fn allows(&self, name: &str) -> bool {
+        self.allow.iter().any(|lint| lint == name)
+    }
+
+    /// Finds the ident of the option `name`, to report errors on it.
+    pub fn ident(&self, name: &str) -> Option<&Ident> {
+        self.idents.iter().find(|ident| *ident == name)
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    str::FromStr,
+};
+
+use proc_macro2::Literal;
+use quote::{quote, ToTokens};
+use syn::{LitStr, Type};
+
+use crate::{constants::FromStrTraitPath, types, TokenStream2};
+
+/// Parses `lit` at expansion time if `ty` is a type this crate knows, returning the expression building the value.
+///
+/// Returns `Ok(None)` for the other types, which are parsed at runtime with `FromStr`.
+pub fn parse(lit: &LitStr, ty: &Type) -> syn::Result<Option<TokenStream2>> {
+    let Some(segment) = types::last_segment(ty).filter(|s| s.arguments.is_none()) else {
+        return Ok(None);
+    };
+
+    let value = lit.value();
+    let name = segment.ident.to_string();
+
+    macro_rules! parse_as {
+        ($ty: ty) => {
+            <$ty>::from_str(&value).map_err(|err| {
+                error!(
+                    lit.span(),
+                    "`{}` isn't a valid `{}` : {}.",
+                    value,
+                    name,
+                    err
+                )
+            })?
+        };
+    }
+
+    macro_rules! number {
+        ($($name: literal => $ty: ty, $suffixed: ident;)*) => {
+            match name.as_str() {
+                $($name => return Ok(Some(Literal::$suffixed(parse_as!($ty)).into_token_stream())),)*
+                _ => (),
+            }
+        };
+    }
+
+    number! {
+        "u8" => u8, u8_suffixed;
+        "u16" => u16, u16_suffixed;
+        "u32" => u32, u32_suffixed;
+        "u64" => u64, u64_suffixed;
+        "u128" => u128, u128_suffixed;
+        "usize" => usize, usize_suffixed;
+        "i8" => i8, i8_suffixed;
+        "i16" => i16, i16_suffixed;
+        "i32" => i32, i32_suffixed;
+        "i64" => i64, i64_suffixed;
+        "i128" => i128, i128_suffixed;
+        "isize" => isize, isize_suffixed;
+    }
+
+    let tokens = match name.as_str() {
+        "f32" => {
+            let value = parse_as!(f32);
+            float(value.into(), Literal::f32_suffixed, value, quote! { f32 })
+        }
+        "f64" => {
+            let value = parse_as!(f64);
+            float(value, Literal::f64_suffixed, value, quote! { f64 })
+        }
+        "bool" => parse_as!(bool).into_token_stream(),
+        "char" => parse_as!(char).into_token_stream(),
+        "Ipv4Addr" => ipv4(parse_as!(Ipv4Addr)),
+        "Ipv6Addr" => ipv6(parse_as!(Ipv6Addr)),
+        "IpAddr" => ip(parse_as!(IpAddr)),
+        "SocketAddrV4" => socket_v4(parse_as!(SocketAddrV4)),
+        "SocketAddrV6" => socket_v6(parse_as!(SocketAddrV6)),
+        "SocketAddr" => match parse_as!(SocketAddr) {
+            SocketAddr::V4(addr) => {
+                let addr = socket_v4(addr);
+                quote! { core::net::SocketAddr::V4(#addr) }
+            }
+            SocketAddr::V6(addr) => {
+                let addr = socket_v6(addr);
+                quote! { core::net::SocketAddr::V6(#addr) }
+            }
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(tokens))
+}
+
+/// Builds the value at runtime with `FromStr`, panicking with the name of the field if the string is invalid.
+pub fn from_str(lit: &LitStr, ty: &Type, field_name: &str) -> TokenStream2 {
+    let message = format!("failed to parse the default value of the field `{}`", field_name);
+
+    quote! { <#ty as #FromStrTraitPath>::from_str(#lit).expect(#message) }
+}
+
+/// Builds a float, using the constants of the float type for the values which have no literal.
+fn float<T>(value: f64, literal: fn(T) -> Literal, raw: T, ty: TokenStream2) -> TokenStream2 {
+    if value.is_nan() {
+        quote! { #ty::NAN }
+    } else if value == f64::INFINITY {
+        quote! { #ty::INFINITY }
+    } else if value == f64::NEG_INFINITY {
+        quote! { #ty::NEG_INFINITY }
+    } else {
+        literal(raw).into_token_stream()
+    }
+}
+
+fn ipv4(addr: Ipv4Addr) -> TokenStream2 {
+    let [a, b, c, d] = addr.octets();
+    quote! { core::net::Ipv4Addr::new(#a, #b, #c, #d) }
+}
+
+fn ipv6(addr: Ipv6Addr) -> TokenStream2 {
+    let segments = addr.segments();
+    quote! { core::net::Ipv6Addr::new(#(#segments),*) }
+}
+
+fn ip(addr: IpAddr) -> TokenStream2 {
+    match addr {
+        IpAddr::V4(addr) => {
+            let addr = ipv4(addr);
+            quote! { core::net::IpAddr::V4(#addr) }
+        }
+        IpAddr::V6(addr) => {
+            let addr = ipv6(addr);
+            quote! { core::net::IpAddr::V6(#addr) }
+        }
+    }
+}
+
+fn socket_v4(addr: SocketAddrV4) -> TokenStream2 {
+    let ip = ipv4(*addr.ip());
+    let port = addr.port();
+
+    quote! { core::net::SocketAddrV4::new(#ip, #port) }
+}
+
+fn socket_v6(addr: SocketAddrV6) -> TokenStream2 {
+    let ip = ipv6(*addr.ip());
+    let port = addr.port();
+    let flowinfo = addr.flowinfo();
+    let scope_id = addr.scope_id();
+
+    quote! { core::net::SocketAddrV6::new(#ip, #port, #flowinfo, #scope_id) }
+}
@@ -0,0 +1,92 @@
+use quote::format_ident;
+use syn::{parse::ParseStream, Attribute, Ident, LitStr, Token};
+
+use crate::constants;
+
+/// Some values, each one belonging to a profile (`None` for the unqualified ones).
+pub struct Profiled<T>(Vec<(Option<LitStr>, T)>);
+
+impl<T> Default for Profiled<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> Profiled<T> {
+    pub fn get(&self, profile: Option<&LitStr>) -> Option<&T> {
+        self.0
+            .iter()
+            .find(|(p, _)| is_same_profile(p.as_ref(), profile))
+            .map(|(_, value)| value)
+    }
+
+    pub fn insert(&mut self, profile: Option<LitStr>, value: T) {
+        self.0.push((profile, value));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&LitStr>, &T)> {
+        self.0.iter().map(|(p, value)| (p.as_ref(), value))
+    }
+
+    pub fn profiles(&self) -> impl Iterator<Item = &LitStr> {
+        self.0.iter().filter_map(|(p, _)| p.as_ref())
+    }
+}
+
+pub fn is_same_profile(a: Option<&LitStr>, b: Option<&LitStr>) -> bool {
+    a.map(LitStr::value) == b.map(LitStr::value)
+}
+
+/// Parses the `profile = "<name>"` header of a default attribute, if there is one.
+///
+/// The separator following the header is left in the stream.
+pub fn parse_profile(input: ParseStream) -> syn::Result<Option<LitStr>> {
+    let fork = input.fork();
+    let is_header = fork
+        .parse::<Ident>()
+        .is_ok_and(|ident| ident == constants::PROFILE_IDENT)
+        && fork.peek(Token![=])
+        && !fork.peek(Token![==]);
+
+    if !is_header {
+        return Ok(None);
+    }
+
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    let name: LitStr = input.parse()?;
+
+    if syn::parse_str::<Ident>(&name.value()).is_err() {
+        return Err(error!(
+            name.span(),
+            "the profile name must be a valid identifier."
+        ));
+    }
+
+    Ok(Some(name))
+}
+
+/// Gets the profile of a default attribute, without checking the rest of the attribute.
+pub fn get_profile(attr: &Attribute) -> syn::Result<Option<LitStr>> {
+    match &attr.meta {
+        syn::Meta::List(list) => list.parse_args_with(|input: ParseStream| {
+            let profile = parse_profile(input)?;
+            input.parse::<proc_macro2::TokenStream>()?;
+
+            Ok(profile)
+        }),
+        _ => Ok(None),
+    }
+}
+
+/// Adds `profile` to `profiles` if it isn't there yet, keeping the declaration order.
+pub fn register(profiles: &mut Vec<LitStr>, profile: &LitStr) {
+    if !profiles.iter().any(|p| p.value() == profile.value()) {
+        profiles.push(profile.clone());
+    }
+}
+
+/// The ident of the constructor generated for `profile`.
+pub fn constructor_ident(profile: &LitStr) -> Ident {
+    format_ident!("{}_{}", constants::DEFAULT_IDENT, profile.value(), span = profile.span())
+}
@@ -0,0 +1,128 @@
+use syn::{spanned::Spanned, visit::Visit, Expr, ExprCall, Ident, Item, Path, Type};
+
+use crate::{constants, diagnostic::Diagnostic, types, Span2};
+
+/// The pointer types whose default value builds the pointed value.
+const POINTER_TYPES: &[&str] = &["Box", "Rc", "Arc"];
+
+/// Whether `path` names the type being derived : `Self` or its own name.
+fn is_self_path(path: &Path, ident: &Ident) -> bool {
+    path.segments.len() == 1 && (path.segments[0].ident == "Self" || path.segments[0].ident == *ident)
+}
+
+/// Whether `ty` is the type being derived.
+fn is_self_type(ty: &Type, ident: &Ident) -> bool {
+    match types::strip(ty) {
+        Type::Path(path) => path.qself.is_none() && is_self_path(&path.path, ident),
+        _ => false,
+    }
+}
+
+/// Whether the default value of `ty` builds the type being derived again,
+/// like `Self`, `Box<Self>` or `(u32, Self)`, but not `Option<Box<Self>>` or `Vec<Self>`, whose default values are empty.
+pub fn is_recursive_type(ty: &Type, ident: &Ident) -> bool {
+    match types::strip(ty) {
+        Type::Array(array) => is_recursive_type(&array.elem, ident),
+        Type::Tuple(tuple) => tuple.elems.iter().any(|elem| is_recursive_type(elem, ident)),
+        ty if is_self_type(ty, ident) => true,
+        ty => types::last_segment(ty)
+            .filter(|segment| POINTER_TYPES.contains(&segment.ident.to_string().as_str()))
+            .and_then(|_| types::first_type_argument(ty))
+            .is_some_and(|ty| is_recursive_type(ty, ident)),
+    }
+}
+
+/// Finds the calls to the `Default` implementation being derived in an expression.
+struct RecursiveCalls<'a> {
+    ident: &'a Ident,
+    /// Whether the expression builds a `Self`, like the value of a `Box<Self>` field after `boxed`,
+    /// making `Default::default()` recursive.
+    is_self_field: bool,
+    calls: Vec<Span2>,
+}
+
+impl RecursiveCalls<'_> {
+    fn is_recursive_call(&self, func: &Expr) -> bool {
+        let Expr::Path(func) = func else {
+            return false;
+        };
+
+        let segments = &func.path.segments;
+        let Some(_) = segments.last().filter(|segment| segment.ident == "default") else {
+            return false;
+        };
+
+        // `<Self as Default>::default()` or `<Self>::default()`
+        if let Some(qself) = &func.qself {
+            return is_self_type(&qself.ty, self.ident);
+        }
+
+        let Some(receiver) = segments.iter().nth_back(1) else {
+            return false;
+        };
+
+        // `Self::default()`, or `Default::default()` into a field of type `Self`
+        (segments.len() == 2 && (receiver.ident == "Self" || receiver.ident == *self.ident))
+            || (self.is_self_field && receiver.ident == "Default")
+    }
+}
+
+impl<'ast> Visit<'ast> for RecursiveCalls<'_> {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if call.args.is_empty() && self.is_recursive_call(&call.func) {
+            self.calls.push(call.func.span());
+        }
+
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    // the nested items have their own `Self`
+    fn visit_item(&mut self, _: &'ast Item) {}
+}
+
+/// Reports the calls to the `Default` implementation of the type `ident` in a default value,
+/// `ty` being the type of the value before its modifiers, if it's known.
+pub fn check_value(expr: &Expr, ty: Option<&Type>, ident: &Ident) -> Vec<Diagnostic> {
+    let mut visitor = RecursiveCalls {
+        ident,
+        is_self_field: ty.is_some_and(|ty| is_self_type(ty, ident)),
+        calls: Vec::new(),
+    };
+    visitor.visit_expr(expr);
+
+    visitor
+        .calls
+        .into_iter()
+        .map(|span| {
+            Diagnostic::new(
+                span,
+                format_args!(
+                    "this calls the `Default` implementation of `{}` it's part of, which recurses infinitely.",
+                    ident
+                ),
+            )
+            .help(allow_help())
+        })
+        .collect()
+}
+
+/// Reports a field without a default value whose type holds the type `ident`, whose default value recurses infinitely.
+pub fn check_field(span: Span2, ident: &Ident) -> Diagnostic {
+    Diagnostic::new(
+        span,
+        format_args!(
+            "the default value of this field builds `{}` with `Default` again, which recurses infinitely.",
+            ident
+        ),
+    )
+    .help("give the field a default value, or wrap its type in an `Option`.")
+    .help(allow_help())
+}
+
+fn allow_help() -> String {
+    format!(
+        "if the recursion stops at runtime, add `{}({})` to the top attribute.",
+        constants::ALLOW_OPTION,
+        constants::RECURSION_LINT
+    )
+}
@@ -3,9 +3,18 @@ use std::{
     fmt::Display,
 };
 
-use syn::{parse::Parse, punctuated::Punctuated, spanned::Spanned, Attribute, Expr, Ident, LitInt, Token};
+use quote::quote;
+use syn::{parse::Parse, spanned::Spanned, Attribute, Fields, Ident, LitInt, Meta, Token};
 
-use crate::{Span2, TokenStream2};
+use crate::{
+    condition::{self, Condition},
+    constants,
+    default::DefaultExpr,
+    derive,
+    diagnostic::{closest, Diagnostic},
+    options::ContainerOptions,
+    profile, Span2, TokenStream2,
+};
 
 enum FieldName {
     Ident(Ident),
@@ -42,15 +51,33 @@ impl Parse for FieldName {
     }
 }
 
+/// A field default value : `<field_id>: <expression>`,
+/// which can be guarded by `cfg` attributes, like its field : `#[cfg(<predicate>)] <field_id>: <expression>`.
 struct FieldAssign {
+    /// The predicates of the `cfg` attributes guarding the value.
+    guards: Vec<Meta>,
     ident: FieldName,
     _colon: Token![:],
-    value: Expr,
+    value: DefaultExpr,
 }
 
 impl Parse for FieldAssign {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut guards = Vec::new();
+        for attr in input.call(Attribute::parse_outer)? {
+            if !attr.path().is_ident(constants::CFG_IDENT) {
+                return Err(error!(
+                    attr.span(),
+                    "only `#[{}(<predicate>)]` can be used on a default value.",
+                    constants::CFG_IDENT
+                ));
+            }
+
+            guards.push(attr.parse_args()?);
+        }
+
         Ok(Self {
+            guards,
             ident: input.parse()?,
             _colon: input.parse()?,
             value: input.parse()?,
@@ -58,22 +85,172 @@ impl Parse for FieldAssign {
     }
 }
 
-fn parse_punctuated_unique(
-    punctuated: Punctuated<FieldAssign, syn::token::Comma>,
+/// The arguments of a top default attribute : `#[default(<item>, ...)]`,
+/// `#[default(profile = "<name>"; <item>, ...)]` or, on a variant, `#[default(<condition>; <item>, ...)]`,
+/// an item being either a field default value (`<field_id>: <expression>`) or an option.
+struct TopAttributeArgs {
+    fields: Vec<FieldAssign>,
+    options: ContainerOptions,
+    condition: Option<Condition>,
+}
+
+impl Parse for TopAttributeArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut fields = Vec::new();
+        let mut options = ContainerOptions::default();
+        let mut condition = None;
+
+        if profile::parse_profile(input)?.is_some() {
+            input.parse::<Token![;]>()?;
+        } else if let Some(parsed) = condition::parse_condition(input)? {
+            condition = Some(parsed);
+
+            // the condition can be alone, like `#[default(cfg(unix))]`
+            if input.is_empty() {
+                return Ok(Self {
+                    fields,
+                    options,
+                    condition,
+                });
+            }
+
+            input.parse::<Token![;]>()?;
+        }
+
+        loop {
+            if input.peek(Token![#]) || (input.peek2(Token![:]) && !input.peek2(Token![::])) {
+                fields.push(input.parse()?);
+            } else {
+                options.parse_option(input)?;
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(Self {
+            fields,
+            options,
+            condition,
+        })
+    }
+}
+
+/// Where a top default attribute is placed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Struct,
+    Enum,
+    Variant,
+}
+
+/// The content of a top default attribute.
+#[derive(Default)]
+pub struct TopAttribute {
+    pub values: HashMap<String, DefaultExpr>,
+    pub options: ContainerOptions,
+    /// The condition selecting the variant as the default one, if there is one.
+    pub condition: Option<Condition>,
+}
+
+/// Reports an unknown field, suggesting the closest field name or giving the valid indices of the unnamed fields.
+fn unknown_field(name: &FieldName, fields: &Fields, field_names: &[String]) -> Diagnostic {
+    let diagnostic = Diagnostic::new(name.span(), format_args!("unknown field `{}`", name));
+
+    match (name, fields) {
+        (_, Fields::Unit) => diagnostic.help("this has no fields."),
+        (FieldName::Ident(ident), Fields::Named(named)) => {
+            let ident = ident.to_string();
+            let Some(candidate) = closest(&ident, field_names.iter().map(String::as_str)) else {
+                return diagnostic;
+            };
+            let declaration = named
+                .named
+                .iter()
+                .find(|field| field.ident.as_ref().is_some_and(|ident| ident == candidate))
+                .unwrap();
+
+            diagnostic
+                .help(format_args!("did you mean `{}`?", candidate))
+                .span_note(
+                    declaration.ident.span(),
+                    format_args!("the field `{}` is declared", candidate),
+                )
+        }
+        (FieldName::IntLiteral(_), Fields::Named(_)) => diagnostic.help(format_args!(
+            "the fields are named, use one of their names : {}.",
+            field_names
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+        (_, Fields::Unnamed(unnamed)) => match unnamed.unnamed.len() {
+            1 => diagnostic.help("there's a single unnamed field, its index is `0`."),
+            len => diagnostic.help(format_args!(
+                "there are {} unnamed fields, use an index in `0..{}`.",
+                len, len
+            )),
+        },
+    }
+}
+
+/// Checks the `cfg` guards of a default value with the compiler, since a field removed by its `cfg` doesn't reach the derive :
+/// an unknown field is only reported when the guards hold, and a known field when they don't.
+fn check_guards(
+    field: &FieldAssign,
+    is_known: bool,
+    fields: &Fields,
     field_names: &[String],
     error_tokens: &mut Vec<TokenStream2>,
-) -> HashMap<String, Expr> {
+) {
+    let guards = &field.guards;
+    let (error, cfg) = if is_known {
+        (
+            error!(
+                field.ident.span(),
+                "the field `{}` exists in this configuration, but the `{}` guarding its default value doesn't hold.",
+                field.ident,
+                constants::CFG_IDENT
+            ),
+            quote! { not(all(#(#guards),*)) },
+        )
+    } else {
+        (
+            unknown_field(&field.ident, fields, field_names).into_error(),
+            quote! { all(#(#guards),*) },
+        )
+    };
+
+    let error = error.into_compile_error();
+    error_tokens.push(quote! {
+        #[cfg(#cfg)]
+        const _: () = { #error };
+    });
+}
+
+fn parse_punctuated_unique(
+    punctuated: Vec<FieldAssign>,
+    fields: &Fields,
+    error_tokens: &mut Vec<TokenStream2>,
+) -> HashMap<String, DefaultExpr> {
+    let field_names = derive::get_fields_name(fields);
+
     let mut hash_map = HashMap::with_capacity(punctuated.len());
     for field in punctuated {
         let ident_str = field.ident.to_string();
 
-        if !field_names.contains(&ident_str) {
-            error!(
-                error_tokens,
-                field.ident.span(),
-                "unknown field `{}`",
-                ident_str
-            );
+        let is_known = field_names.contains(&ident_str);
+        if !field.guards.is_empty() {
+            check_guards(&field, is_known, fields, &field_names, error_tokens);
+        } else if !is_known {
+            unknown_field(&field.ident, fields, &field_names).emit(error_tokens);
+        }
+
+        if !is_known {
             continue;
         }
 
@@ -95,15 +272,17 @@ fn parse_punctuated_unique(
 
 pub fn get_default_values(
     attr: &Attribute,
-    field_names: &[String],
-    require_list: bool,
+    fields: &Fields,
+    location: Location,
+    is_profiled: bool,
     error_tokens: &mut Vec<TokenStream2>,
-) -> Option<HashMap<String, Expr>> {
-    let list = if require_list {
+) -> Option<TopAttribute> {
+    // the unqualified attribute of a variant can be a single `#[default]`
+    let list = if location != Location::Variant || is_profiled {
         handle_error!(attr.meta.require_list(), error_tokens)?
     } else {
         match &attr.meta {
-            syn::Meta::Path(_) => return None,
+            syn::Meta::Path(_) => return Some(TopAttribute::default()),
             syn::Meta::List(list) => list,
             syn::Meta::NameValue(nv) => {
                 let ident = attr.path().get_ident().unwrap();
@@ -118,11 +297,47 @@ pub fn get_default_values(
         }
     };
 
-    let punctuated: Punctuated<FieldAssign, Token![,]> = handle_error!(
-        list.parse_args_with(Punctuated::parse_separated_nonempty),
-        error_tokens
-    )?;
+    let args: TopAttributeArgs = handle_error!(list.parse_args(), error_tokens)?;
+
+    if location == Location::Enum {
+        for field in &args.fields {
+            error!(
+                error_tokens,
+                field.ident.span(),
+                "default values are not allowed in the top attribute of an enum, put them on the default variant instead."
+            );
+        }
+    }
+
+    if let Some(condition) = args.condition.as_ref().filter(|_| location != Location::Variant) {
+        error!(
+            error_tokens,
+            condition.span(),
+            "the conditions can only be used on the variants of an enum."
+        );
+    }
+
+    if location == Location::Variant {
+        for ident in &args.options.idents {
+            error!(
+                error_tokens,
+                ident.span(),
+                "options are only allowed in the top attribute of the struct or enum."
+            );
+        }
+    } else if is_profiled {
+        for ident in &args.options.idents {
+            error!(
+                error_tokens,
+                ident.span(),
+                "options are not allowed in a profiled attribute."
+            );
+        }
+    }
 
-    let hash_map = parse_punctuated_unique(punctuated, field_names, error_tokens);
-    Some(hash_map)
+    Some(TopAttribute {
+        values: parse_punctuated_unique(args.fields, fields, error_tokens),
+        options: args.options,
+        condition: args.condition,
+    })
 }
@@ -0,0 +1,56 @@
+use quote::{format_ident, quote};
+use syn::Fields;
+
+use crate::{
+    default::{self, DefaultValue},
+    Span2, TokenStream2,
+};
+
+/// Builds the value field by field, each field being computed behind a guard
+/// which reports the field if a panic unwinds through it.
+pub fn build(
+    path: &TokenStream2,
+    name: &str,
+    mut values: Vec<DefaultValue>,
+    fields: &Fields,
+) -> TokenStream2 {
+    let bindings: Vec<_> = values
+        .iter_mut()
+        .enumerate()
+        .map(|(i, value)| {
+            let binding = format_ident!("field_{}", i, span = Span2::mixed_site());
+            let guard = format_ident!("_guard", span = Span2::mixed_site());
+            let field_path = format!("{}::{}", name, value.name);
+            let field_value = std::mem::replace(&mut value.value, quote! { #binding });
+            let cfgs = &value.cfgs;
+
+            quote! {
+                #(#cfgs)*
+                let #binding = {
+                    let #guard = __BetterDefaultPanicGuard(#field_path);
+                    #field_value
+                };
+            }
+        })
+        .collect();
+
+    let body = default::derive_body(values, fields);
+
+    quote! {
+        {
+            struct __BetterDefaultPanicGuard(&'static str);
+
+            impl ::core::ops::Drop for __BetterDefaultPanicGuard {
+                fn drop(&mut self) {
+                    if ::std::thread::panicking() {
+                        ::std::eprintln!("while computing default of {}", self.0);
+                    }
+                }
+            }
+
+            #(#bindings)*
+
+            #path #body
+        }
+    }
+}
@@ -1,26 +1,29 @@
-use crate::TokenStream2;
+use crate::{Span2, TokenStream2};
+use proc_macro2::{Group, TokenTree};
 use quote::ToTokens;
 
-pub trait JoinTokens {
-    fn join_tokens<S: ToTokens>(&self, separator: &S) -> TokenStream2;
+pub trait Respan {
+    /// Gets the tokens with all their spans set to `span`, so that the errors on them point at the user's code.
+    fn respanned(&self, span: Span2) -> TokenStream2;
 }
 
-impl<T: ToTokens> JoinTokens for Vec<T> {
-    fn join_tokens<S: ToTokens>(&self, separator: &S) -> TokenStream2 {
-        let mut tokens = TokenStream2::new();
+fn respan_stream(tokens: TokenStream2, span: Span2) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let stream = respan_stream(group.stream(), span);
+                token = TokenTree::Group(Group::new(group.delimiter(), stream));
+            }
 
-        if self.is_empty() {
-            return tokens;
-        }
-
-        let mut iter = self.iter();
-        iter.next().to_tokens(&mut tokens);
-
-        for el in iter {
-            separator.to_tokens(&mut tokens);
-            el.to_tokens(&mut tokens);
-        }
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
 
-        tokens
+impl<T: ToTokens> Respan for T {
+    fn respanned(&self, span: Span2) -> TokenStream2 {
+        respan_stream(self.to_token_stream(), span)
     }
 }
@@ -0,0 +1,158 @@
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::{
+    constants::{self, DefaultTraitPath},
+    default::{DefaultValue, Selection},
+    default_with, derive,
+    options::ContainerOptions,
+    Span2, TokenStream2,
+};
+
+/// Checks that the `TryDefault` options aren't used with a derive building an infallible default value.
+pub fn check_infallible(options: &ContainerOptions, error_tokens: &mut Vec<TokenStream2>) {
+    for name in [
+        constants::ERROR_OPTION,
+        constants::FIELD_ERROR_OPTION,
+        constants::IMPL_DEFAULT_OPTION,
+    ] {
+        if let Some(ident) = options.ident(name) {
+            error!(
+                error_tokens,
+                ident.span(),
+                "this option is only supported by the `TryDefault` derive, use its `{}` option to implement `Default`.",
+                constants::IMPL_DEFAULT_OPTION
+            );
+        }
+    }
+}
+
+/// Evaluates the value of a field in a closure, so the errors propagated with `?` can be handled.
+fn catch_field_error(value: &DefaultValue, error_ty: &syn::Type) -> TokenStream2 {
+    let ty = &value.ty;
+    let value = &value.value;
+
+    quote! {
+        (|| -> ::core::result::Result<#ty, #error_ty> {
+            ::core::result::Result::Ok(#value)
+        })()
+    }
+}
+
+pub fn derive(input: &DeriveInput) -> TokenStream2 {
+    if let Some(error_tokens) = derive::reject_union(input) {
+        return error_tokens;
+    }
+
+    let mut error_tokens = Vec::new();
+
+    // the errors of the context are reported by the `DefaultWith` derive
+    let context = default_with::get_context(input, &mut Vec::new());
+    let default_fields = derive::get_default_fields(
+        input,
+        context.as_ref().map(|c| &c.ident),
+        &mut error_tokens,
+    );
+
+    let Some(default_fields) = default_fields else {
+        return error_tokens.into_iter().collect();
+    };
+    default_fields.reject_conditional("TryDefault", &mut error_tokens);
+
+    let options = &default_fields.options;
+    let Some(error_ty) = &options.error else {
+        // an invalid top attribute already has its own errors
+        if error_tokens.is_empty() {
+            error!(
+                error_tokens,
+                Span2::call_site(),
+                "this derive requires the error type in the top attribute : `#[{}({} = <type>)]`.",
+                constants::DEFAULT_IDENT,
+                constants::ERROR_OPTION
+            );
+        }
+
+        return error_tokens.into_iter().collect();
+    };
+
+    let try_values = default_fields
+        .values(Selection::default())
+        .into_iter()
+        .map(|mut value| {
+            let Some(field_error) = options.field_error.as_ref().filter(|_| value.is_override) else {
+                // without `field_error`, `?` directly returns from `try_default`
+                return value;
+            };
+
+            let name = &value.name;
+            let result = catch_field_error(&value, error_ty);
+            value.value = quote! {
+                match #result {
+                    ::core::result::Result::Ok(value) => value,
+                    ::core::result::Result::Err(error) => {
+                        return ::core::result::Result::Err((#field_error)(#name, error))
+                    }
+                }
+            };
+
+            value
+        })
+        .collect();
+    let try_body = default_fields.build(try_values);
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let default_tokens = options.impl_default.as_ref().map(|_| {
+        let values = default_fields
+            .values(Selection::default())
+            .into_iter()
+            .map(|mut value| {
+                if !value.is_override {
+                    return value;
+                }
+
+                let message = format!(
+                    "failed to compute the default value of `{}::{}`: {{:?}}",
+                    default_fields.name, value.name
+                );
+                let result = catch_field_error(&value, error_ty);
+                value.value = quote! {
+                    match #result {
+                        ::core::result::Result::Ok(value) => value,
+                        ::core::result::Result::Err(error) => ::core::panic!(#message, error),
+                    }
+                };
+
+                value
+            })
+            .collect();
+        let body = default_fields.build(values);
+
+        quote! {
+            impl #impl_generics #DefaultTraitPath for #ident #type_generics #where_clause {
+                #[allow(clippy::redundant_closure_call)]
+                fn default() -> Self {
+                    #body
+                }
+            }
+        }
+    });
+
+    let error_tokens: TokenStream2 = error_tokens.into_iter().collect();
+
+    quote! {
+        impl #impl_generics #ident #type_generics #where_clause {
+            /// Returns the default value, or the error of the first field which failed to be computed.
+            #[allow(clippy::redundant_closure_call)]
+            #vis fn try_default() -> ::core::result::Result<Self, #error_ty> {
+                ::core::result::Result::Ok(#try_body)
+            }
+        }
+
+        #default_tokens
+
+        #error_tokens
+    }
+}
@@ -0,0 +1,194 @@
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Type};
+
+use crate::{
+    constants::{self, ArrayFromFnPath, DefaultTraitPath, NonePath, NullMutPath, NullPath},
+    traits::Respan,
+    TokenStream2,
+};
+
+pub const NON_ZERO_TYPES: &[&str] = &[
+    "NonZero",
+    "NonZeroU8",
+    "NonZeroU16",
+    "NonZeroU32",
+    "NonZeroU64",
+    "NonZeroU128",
+    "NonZeroUsize",
+    "NonZeroI8",
+    "NonZeroI16",
+    "NonZeroI32",
+    "NonZeroI64",
+    "NonZeroI128",
+    "NonZeroIsize",
+];
+
+/// Whether the default value of `ty` is the one of its `Default` implementation, which core provides for
+/// arrays up to 32 elements and tuples up to 12 elements of such types.
+///
+/// With `auto`, the types having an `auto` default value use it instead.
+fn is_default(ty: &Type, auto: bool) -> bool {
+    match ty {
+        Type::Array(array) => {
+            let is_short = match &array.len {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(len),
+                    ..
+                }) => len.base10_parse::<usize>().is_ok_and(|len| len <= 32),
+                _ => false,
+            };
+
+            is_short && is_default(&array.elem, auto)
+        }
+        Type::Tuple(tuple) => {
+            tuple.elems.len() <= 12 && tuple.elems.iter().all(|elem| is_default(elem, auto))
+        }
+        Type::Group(group) => is_default(&group.elem, auto),
+        Type::Paren(paren) => is_default(&paren.elem, auto),
+        _ => !auto || !matches!(auto_value(ty), Ok(Some(_))),
+    }
+}
+
+/// Gets the default value of a type.
+///
+/// Arrays and tuples are built element by element when their `Default` implementation can't be used,
+/// since core only implements `Default` for arrays up to 32 elements (and not for a generic length)
+/// and tuples up to 12 elements.
+///
+/// With `auto`, the types which don't implement `Default` but have a sensible default value
+/// (see [`auto_value`]) use this value.
+pub fn default_value(ty: &Type, auto: bool) -> TokenStream2 {
+    if is_default(ty, auto) {
+        let default = DefaultTraitPath.respanned(ty.span());
+        return quote_spanned! { ty.span()=> <#ty as #default>::default() };
+    }
+
+    match ty {
+        Type::Array(array) => {
+            let elem = default_value(&array.elem, auto);
+            let from_fn = ArrayFromFnPath.respanned(ty.span());
+            quote_spanned! { ty.span()=> #from_fn(|_| #elem) }
+        }
+        Type::Tuple(tuple) => {
+            let elems = tuple.elems.iter().map(|elem| default_value(elem, auto));
+            quote_spanned! { ty.span()=> (#(#elems,)*) }
+        }
+        Type::Group(group) => default_value(&group.elem, auto),
+        Type::Paren(paren) => default_value(&paren.elem, auto),
+        // the types without an `auto` default value are handled by `is_default`
+        _ => auto_value(ty).ok().flatten().unwrap().respanned(ty.span()),
+    }
+}
+
+/// Gets the `auto` default value of a type which doesn't implement `Default`, `None` if the type isn't one of them.
+///
+/// Returns an error for the types which have no sensible default value, like function pointers.
+fn auto_value(ty: &Type) -> syn::Result<Option<TokenStream2>> {
+    let value = match ty {
+        Type::Ptr(ptr) if ptr.mutability.is_some() => quote! { #NullMutPath() },
+        Type::Ptr(_) => quote! { #NullPath() },
+        Type::Reference(reference) => match strip(&reference.elem) {
+            Type::Path(path) if reference.mutability.is_none() && path.path.is_ident("str") => {
+                quote! { "" }
+            }
+            Type::Slice(_) if reference.mutability.is_some() => quote! { &mut [] },
+            Type::Slice(_) => quote! { &[] },
+            _ => return Ok(None),
+        },
+        Type::Path(_) => {
+            let Some(segment) = last_segment(ty) else {
+                return Ok(None);
+            };
+
+            let name = segment.ident.to_string();
+            match name.as_str() {
+                "NonNull" => quote! { <#ty>::dangling() },
+                "Option" => quote! { #NonePath },
+                _ if NON_ZERO_TYPES.contains(&name.as_str()) => quote! { <#ty>::MIN },
+                _ => return Ok(None),
+            }
+        }
+        Type::BareFn(_) => {
+            return Err(error!(
+                ty.span(),
+                "function pointers have no default value, use `#[{}(<expression>)]` instead.",
+                constants::DEFAULT_IDENT
+            ))
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(value.into_token_stream()))
+}
+
+/// Reports the types without any `auto` default value in `ty`, including the elements of arrays and tuples.
+pub fn check_auto(ty: &Type, error_tokens: &mut Vec<TokenStream2>) {
+    match strip(ty) {
+        Type::Array(array) => check_auto(&array.elem, error_tokens),
+        Type::Tuple(tuple) => {
+            for elem in &tuple.elems {
+                check_auto(elem, error_tokens);
+            }
+        }
+        ty => {
+            handle_error!(auto_value(ty), error_tokens);
+        }
+    }
+}
+
+/// Gets the last segment of a path type, like `Option` for `core::option::Option<T>`.
+pub fn last_segment(ty: &Type) -> Option<&syn::PathSegment> {
+    match strip(ty) {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last(),
+        _ => None,
+    }
+}
+
+/// Gets the type wrapped by `modifiers` in `ty`, like `T` in `Option<T>` for `some`.
+///
+/// Returns `None` after an `into`, since the converted type is unknown.
+pub fn wrapped_type<'a>(ty: &'a Type, modifiers: &[syn::Ident]) -> Option<&'a Type> {
+    let mut ty = ty;
+    for modifier in modifiers {
+        if modifier == constants::INTO_MODIFIER {
+            return None;
+        }
+
+        ty = first_type_argument(ty)?;
+    }
+
+    Some(ty)
+}
+
+/// Gets the first type argument of a path type, like `T` in `Option<T>`.
+pub fn first_type_argument(ty: &Type) -> Option<&Type> {
+    let syn::PathArguments::AngleBracketed(args) = &last_segment(ty)?.arguments else {
+        return None;
+    };
+
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Displays a type like it's usually written, without the spaces around `<`, `>` and `::`.
+pub fn display(ty: &Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace(" :: ", "::")
+        .replace("& ", "&")
+}
+
+/// Removes the invisible groups and parentheses around a type.
+pub fn strip(ty: &Type) -> &Type {
+    match ty {
+        Type::Group(group) => strip(&group.elem),
+        Type::Paren(paren) => strip(&paren.elem),
+        _ => ty,
+    }
+}
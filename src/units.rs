@@ -0,0 +1,154 @@
+use proc_macro2::Literal;
+use quote::{quote, ToTokens};
+use syn::{LitStr, Type};
+
+use crate::{constants::DurationPath, types, TokenStream2};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+const DURATION_UNITS: &[(&str, u128)] = &[
+    ("ms", 1_000_000),
+    ("s", NANOS_PER_SEC),
+    ("m", 60 * NANOS_PER_SEC),
+    ("h", 60 * 60 * NANOS_PER_SEC),
+    ("d", 24 * 60 * 60 * NANOS_PER_SEC),
+];
+
+const BYTE_UNITS: &[(&str, u128)] = &[
+    ("B", 1),
+    ("KB", 1_000),
+    ("KiB", 1 << 10),
+    ("MB", 1_000_000),
+    ("MiB", 1 << 20),
+    ("GB", 1_000_000_000),
+    ("GiB", 1 << 30),
+];
+
+/// The integer types, with their maximum value.
+pub const INTEGER_TYPES: &[(&str, u128)] = &[
+    ("u8", u8::MAX as u128),
+    ("u16", u16::MAX as u128),
+    ("u32", u32::MAX as u128),
+    ("u64", u64::MAX as u128),
+    ("u128", u128::MAX),
+    ("usize", u64::MAX as u128),
+    ("i8", i8::MAX as u128),
+    ("i16", i16::MAX as u128),
+    ("i32", i32::MAX as u128),
+    ("i64", i64::MAX as u128),
+    ("i128", i128::MAX as u128),
+    ("isize", i64::MAX as u128),
+];
+
+/// Parses a quantity made of one or more `<number><unit>` components, like `1h 30m` or `1.5s`,
+/// into a number of `precision` (nanoseconds or bytes).
+fn parse_quantity(
+    lit: &LitStr,
+    units: &[(&str, u128)],
+    kind: &str,
+    precision: &str,
+) -> syn::Result<u128> {
+    let value = lit.value();
+    let unit_names = units.iter().map(|(name, _)| *name).collect::<Vec<_>>().join("`, `");
+    let invalid = |reason: &str| {
+        error!(
+            lit.span(),
+            "`{}` isn't a valid {} : {}.",
+            value,
+            kind,
+            reason
+        )
+    };
+
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(invalid("it is empty"));
+    }
+
+    let mut total: u128 = 0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '_')
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(number_len);
+        let unit_len = tail
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        rest = tail.trim_start();
+
+        let number = number.replace('_', "");
+        let (integer, fraction) = number.split_once('.').unwrap_or((&number, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(invalid("a number is expected before each unit"));
+        }
+
+        let Some((_, factor)) = units.iter().find(|(name, _)| *name == unit) else {
+            return Err(invalid(&format!("the unit must be one of `{}`", unit_names)));
+        };
+
+        let overflow = || invalid("it is too large");
+        let parse = |digits: &str| -> syn::Result<u128> {
+            if digits.is_empty() {
+                return Ok(0);
+            }
+
+            digits.parse().map_err(|_| overflow())
+        };
+
+        let scale = 10u128
+            .checked_pow(fraction.len() as u32)
+            .ok_or_else(overflow)?;
+        let fraction_value = parse(fraction)?.checked_mul(*factor).ok_or_else(overflow)?;
+        if fraction_value % scale != 0 {
+            return Err(invalid(&format!("it isn't a whole number of {}", precision)));
+        }
+
+        total = parse(integer)?
+            .checked_mul(*factor)
+            .and_then(|v| v.checked_add(fraction_value / scale))
+            .and_then(|v| v.checked_add(total))
+            .ok_or_else(overflow)?;
+    }
+
+    Ok(total)
+}
+
+/// Parses a duration, like `30s` or `1h 30m`, into `Duration::new(<secs>, <nanos>)`.
+pub fn duration(lit: &LitStr) -> syn::Result<TokenStream2> {
+    let nanos = parse_quantity(lit, DURATION_UNITS, "duration", "nanoseconds")?;
+
+    let secs = u64::try_from(nanos / NANOS_PER_SEC)
+        .map_err(|_| error!(lit.span(), "`{}` is too large for a `Duration`.", lit.value()))?;
+    let nanos = (nanos % NANOS_PER_SEC) as u32;
+
+    Ok(quote! { #DurationPath::new(#secs, #nanos) })
+}
+
+/// Parses a byte size, like `64MiB`, into an integer literal.
+///
+/// If `ty` is an integer type, the literal has its type and is checked against its maximum.
+pub fn bytes(lit: &LitStr, ty: Option<&Type>) -> syn::Result<TokenStream2> {
+    let bytes = parse_quantity(lit, BYTE_UNITS, "byte size", "bytes")?;
+
+    let integer = ty
+        .and_then(types::last_segment)
+        .filter(|s| s.arguments.is_none())
+        .and_then(|s| INTEGER_TYPES.iter().find(|(name, _)| s.ident == name));
+
+    let Some((name, max)) = integer else {
+        return Ok(Literal::u128_unsuffixed(bytes).into_token_stream());
+    };
+
+    if bytes > *max {
+        return Err(error!(
+            lit.span(),
+            "`{}` ({} bytes) doesn't fit in a `{}`.",
+            lit.value(),
+            bytes,
+            name
+        ));
+    }
+
+    Ok(syn::LitInt::new(&format!("{}{}", bytes, name), lit.span()).into_token_stream())
+}
@@ -0,0 +1,177 @@
+use std::cmp::Ordering;
+
+use quote::{format_ident, quote, ToTokens};
+use syn::{spanned::Spanned, Expr, ExprRange, Lit, RangeLimits, UnOp};
+
+use crate::{constants, Span2, TokenStream2};
+
+/// The value of a number literal.
+#[derive(Clone, Copy)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn neg(self) -> Option<Self> {
+        match self {
+            Self::Int(int) => int.checked_neg().map(Self::Int),
+            Self::Float(float) => Some(Self::Float(-float)),
+        }
+    }
+
+    /// Converts the number to a float, `None` if an integer isn't exactly representable.
+    fn to_float(self) -> Option<f64> {
+        // the integers up to 2^53 are exact in a `f64`
+        const MAX_EXACT: i128 = 1 << f64::MANTISSA_DIGITS;
+
+        match self {
+            Self::Int(int) if int.abs() <= MAX_EXACT => Some(int as f64),
+            Self::Int(_) => None,
+            Self::Float(float) => Some(float),
+        }
+    }
+
+    /// Compares two numbers, `None` if the comparison wouldn't be exact.
+    fn compare(self, other: Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => Some(a.cmp(&b)),
+            (a, b) => a.to_float()?.partial_cmp(&b.to_float()?),
+        }
+    }
+}
+
+/// Evaluates an integer or float literal, possibly negated.
+fn evaluate(expr: &Expr) -> Option<Number> {
+    match expr {
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => evaluate(&unary.expr)?.neg(),
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse().ok().map(Number::Int),
+            Lit::Float(float) => float.base10_parse().ok().map(Number::Float),
+            _ => None,
+        },
+        Expr::Paren(paren) => evaluate(&paren.expr),
+        Expr::Group(group) => evaluate(&group.expr),
+        _ => None,
+    }
+}
+
+/// Displays a value or a range like it's usually written, without the spaces around `..` and after a leading `-`.
+fn display(tokens: &impl ToTokens) -> String {
+    let text = tokens
+        .to_token_stream()
+        .to_string()
+        .replace(" ..", "..")
+        .replace(".. ", "..")
+        .replace("..= ", "..=")
+        .replace("..- ", "..-")
+        .replace("..=- ", "..=-");
+
+    match text.strip_prefix("- ") {
+        Some(text) => format!("-{}", text),
+        None => text,
+    }
+}
+
+/// Whether `value` is in `range`, `None` if the value or the bounds aren't literals,
+/// or if they can't be compared exactly.
+fn contains(range: &ExprRange, value: &Expr) -> Option<bool> {
+    let value = evaluate(value)?;
+    let start = range.start.as_deref().map(evaluate);
+    let end = range.end.as_deref().map(evaluate);
+
+    let above_start = match start {
+        Some(start) => value.compare(start?)?.is_ge(),
+        None => true,
+    };
+    let below_end = match (end, &range.limits) {
+        (Some(end), RangeLimits::HalfOpen(_)) => value.compare(end?)?.is_lt(),
+        (Some(end), RangeLimits::Closed(_)) => value.compare(end?)?.is_le(),
+        (None, _) => true,
+    };
+
+    Some(above_start && below_end)
+}
+
+/// Checks at expansion time that the literal `value` is in `range`.
+pub fn check_range(range: &ExprRange, value: &Expr) -> syn::Result<()> {
+    if contains(range, value) == Some(false) {
+        return Err(error!(
+            value.span(),
+            "the default value `{}` is out of the range `{}`.",
+            display(value),
+            display(range)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks in debug builds that the value built by `tokens` is in `range`, unless it's checked at expansion time.
+pub fn range_tokens(
+    range: &ExprRange,
+    value: &Expr,
+    tokens: TokenStream2,
+    field_name: &str,
+) -> TokenStream2 {
+    if contains(range, value).is_some() {
+        return tokens;
+    }
+
+    let binding = format_ident!("value", span = Span2::mixed_site());
+    let message = format!(
+        "the default value of the field `{}` is out of the range `{}`",
+        field_name,
+        display(range)
+    );
+
+    quote! {
+        {
+            let #binding = #tokens;
+            debug_assert!((#range).contains(&#binding), #message);
+            #binding
+        }
+    }
+}
+
+/// Runs the `validate` function on the value at `value`, in debug builds, panicking with its error.
+pub fn validate_tokens(validate: &Expr, value: TokenStream2, type_name: &str) -> TokenStream2 {
+    let message = format!("invalid default value of `{}`: {{}}", type_name);
+    let function = format_ident!("validate", span = Span2::mixed_site());
+    let error = format_ident!("error", span = Span2::mixed_site());
+
+    quote! {
+        if cfg!(debug_assertions) {
+            let #function: fn(&Self) -> ::core::result::Result<(), _> = #validate;
+            if let ::core::result::Result::Err(#error) = #function(#value) {
+                ::core::panic!(#message, #error);
+            }
+        }
+    }
+}
+
+/// Builds the value with `tokens`, then validates it with the `validate` option.
+pub fn validate_body(validate: &Expr, tokens: TokenStream2, type_name: &str) -> TokenStream2 {
+    let binding = format_ident!("value", span = Span2::mixed_site());
+    let validation = validate_tokens(validate, quote! { &#binding }, type_name);
+
+    quote! {
+        {
+            let #binding = #tokens;
+            #validation
+            #binding
+        }
+    }
+}
+
+/// Parses the `range = <range>` following a field value.
+pub fn parse_range(input: syn::parse::ParseStream) -> syn::Result<Option<ExprRange>> {
+    if crate::default::parse_keyword(input, constants::RANGE_IDENT)?.is_none() {
+        return Ok(None);
+    }
+
+    match input.parse()? {
+        Expr::Range(range) => Ok(Some(range)),
+        expr => Err(error!(expr.span(), "expected a range, like `1..=10`.")),
+    }
+}
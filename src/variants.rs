@@ -0,0 +1,178 @@
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, visit::Visit, Generics, Ident, Type};
+
+use crate::{
+    constants::{self, DefaultTraitPath},
+    default::DefaultValue,
+    derive::VariantDefaults,
+    TokenStream2,
+};
+
+/// Converts a variant name to snake case, like `http_server` for `HTTPServer`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+
+            // a new word starts after a lowercase letter or a digit, or at the last capital of an acronym
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lowercase)
+            {
+                snake.push('_');
+            }
+        }
+
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+/// The ident of the constructor generated for the variant `ident`.
+pub fn constructor_ident(ident: &Ident) -> Ident {
+    let name = ident.to_string();
+    let name = name.strip_prefix("r#").unwrap_or(&name);
+
+    format_ident!(
+        "{}_{}",
+        constants::DEFAULT_IDENT,
+        snake_case(name),
+        span = ident.span()
+    )
+}
+
+/// Finds whether a type mentions one of the type parameters.
+struct TypeParams<'a> {
+    generics: &'a Generics,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for TypeParams<'_> {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if path.leading_colon.is_none()
+            && self.generics.type_params().any(|param| {
+                path.segments
+                    .first()
+                    .is_some_and(|s| s.ident == param.ident)
+            })
+        {
+            self.found = true;
+        }
+
+        syn::visit::visit_path(self, path);
+    }
+}
+
+/// Adds the `Default` bounds needed by the fields built with the default value of their type,
+/// for the types using the type parameters, which the `Default` implementation doesn't require.
+///
+/// The arrays and tuples are built element by element, so the bounds are on their elements.
+fn push_bounds(ty: &Type, generics: &Generics, bounds: &mut Vec<TokenStream2>) {
+    match ty {
+        Type::Array(array) => push_bounds(&array.elem, generics, bounds),
+        Type::Tuple(tuple) => {
+            for elem in &tuple.elems {
+                push_bounds(elem, generics, bounds);
+            }
+        }
+        Type::Group(group) => push_bounds(&group.elem, generics, bounds),
+        Type::Paren(paren) => push_bounds(&paren.elem, generics, bounds),
+        _ => {
+            let mut params = TypeParams {
+                generics,
+                found: false,
+            };
+            params.visit_type(ty);
+
+            let bound = quote_spanned! { ty.span()=> #ty: #DefaultTraitPath };
+            if params.found && !bounds.iter().any(|b| b.to_string() == bound.to_string()) {
+                bounds.push(bound);
+            }
+        }
+    }
+}
+
+fn bounds(values: &[DefaultValue], generics: &Generics) -> Vec<TokenStream2> {
+    let mut bounds = Vec::new();
+    for value in values.iter().filter(|v| !v.is_override && !v.auto) {
+        push_bounds(&value.ty, generics, &mut bounds);
+    }
+
+    bounds
+}
+
+/// Generates the `default_<variant>` constructors, and `variant_defaults` returning all of them
+/// when `all` the variants have one.
+pub fn functions(
+    enum_ident: &Ident,
+    vis: &syn::Visibility,
+    generics: &Generics,
+    variants: &[VariantDefaults],
+    all: bool,
+) -> TokenStream2 {
+    let mut all_bounds = Vec::new();
+    let constructors = variants.iter().map(|variant| {
+        let fn_ident = constructor_ident(&variant.ident);
+        let doc = format!(
+            "Returns the `{}::{}` variant with its default values.",
+            enum_ident, variant.ident
+        );
+
+        let (body, bounds) = match &variant.fields {
+            Some(fields) => {
+                let values = fields.values(Default::default());
+                let bounds = bounds(&values, generics);
+
+                (fields.build(values), bounds)
+            }
+            None => (
+                quote! { <Self as #DefaultTraitPath>::default() },
+                Vec::new(),
+            ),
+        };
+        let where_clause = (!bounds.is_empty()).then(|| quote! { where #(#bounds,)* });
+        for bound in bounds {
+            if !all_bounds
+                .iter()
+                .any(|b: &TokenStream2| b.to_string() == bound.to_string())
+            {
+                all_bounds.push(bound);
+            }
+        }
+
+        quote! {
+            #[doc = #doc]
+            #vis fn #fn_ident() -> Self #where_clause {
+                #body
+            }
+        }
+    });
+    let constructors: Vec<_> = constructors.collect();
+    if !all {
+        return quote! { #(#constructors)* };
+    }
+
+    let len = variants.len();
+    let fn_idents = variants
+        .iter()
+        .map(|variant| constructor_ident(&variant.ident));
+    let where_clause = (!all_bounds.is_empty()).then(|| quote! { where #(#all_bounds,)* });
+    let doc = format!(
+        "Returns every variant of `{}` with its default values, in declaration order.",
+        enum_ident
+    );
+
+    quote! {
+        #(#constructors)*
+
+        #[doc = #doc]
+        #vis fn variant_defaults() -> [Self; #len] #where_clause {
+            [#(Self::#fn_idents()),*]
+        }
+    }
+}
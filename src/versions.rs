@@ -0,0 +1,125 @@
+use quote::{format_ident, quote};
+use syn::{parse::ParseStream, Attribute, Ident, LitInt, Token};
+
+use crate::{
+    constants,
+    default::{self, DefaultExpr},
+    diagnostic::Diagnostic,
+    profile, Span2, TokenStream2,
+};
+
+/// A default value replacing the older ones from a version on : `#[default(since = <version>, <value>)]`.
+pub struct VersionedValue {
+    pub since: LitInt,
+    pub value: DefaultExpr,
+}
+
+impl VersionedValue {
+    pub fn version(&self) -> u32 {
+        // the version is checked when parsing `since`
+        self.since.base10_parse().unwrap()
+    }
+}
+
+/// Parses the `since = <version>` header of a field attribute, if there is one.
+///
+/// The separator following the header is left in the stream.
+pub fn parse_since(input: ParseStream) -> syn::Result<Option<LitInt>> {
+    if default::parse_keyword(input, constants::SINCE_IDENT)?.is_none() {
+        return Ok(None);
+    }
+
+    let since: LitInt = input.parse()?;
+    match since.base10_parse::<u32>() {
+        Ok(0) => Err(error!(
+            since.span(),
+            "the versions start at 1, the value without `{}` being the value of the version 0.",
+            constants::SINCE_IDENT
+        )),
+        Ok(_) => Ok(Some(since)),
+        Err(_) => Err(error!(since.span(), "the version must be a `u32`.")),
+    }
+}
+
+/// Whether a field attribute has a `since` header, without checking the rest of the attribute.
+pub fn is_versioned(attr: &Attribute) -> bool {
+    let syn::Meta::List(list) = &attr.meta else {
+        return false;
+    };
+
+    list.parse_args_with(|input: ParseStream| {
+        if profile::parse_profile(input)?.is_some() {
+            input.parse::<Token![,]>()?;
+        }
+        let is_versioned = default::parse_keyword(input, constants::SINCE_IDENT)?.is_some();
+        input.parse::<TokenStream2>()?;
+
+        Ok(is_versioned)
+    })
+    .unwrap_or(false)
+}
+
+/// Adds the value of a version to the values of a field, which must be declared from the oldest to the newest.
+pub fn push(versions: &mut Vec<VersionedValue>, value: VersionedValue, error_tokens: &mut Vec<TokenStream2>) {
+    if let Some(previous) = versions.last() {
+        let (version, previous_version) = (value.version(), previous.version());
+
+        if version <= previous_version {
+            let message = if version == previous_version {
+                format!("the default value of the version {} is already declared.", version)
+            } else {
+                format!(
+                    "the versions must be declared in increasing order, but the version {} follows the version {}.",
+                    version, previous_version
+                )
+            };
+
+            Diagnostic::new(value.since.span(), message)
+                .span_note(
+                    previous.since.span(),
+                    format!("the version {} is declared", previous_version),
+                )
+                .emit(error_tokens);
+
+            return;
+        }
+    }
+
+    versions.push(value);
+}
+
+/// The ident of the parameter of `default_for_version`.
+pub fn parameter() -> Ident {
+    format_ident!("version", span = Span2::mixed_site())
+}
+
+/// Chains the values of the versions, the newest one being checked first,
+/// down to `base`, the value of the version 0.
+pub fn select(version: &Ident, versions: Vec<(u32, TokenStream2)>, base: TokenStream2) -> TokenStream2 {
+    versions.into_iter().fold(base, |older, (since, value)| {
+        quote! {
+            if #version >= #since {
+                #value
+            } else {
+                #older
+            }
+        }
+    })
+}
+
+/// Generates `DEFAULTS_VERSION`, the newest version `latest`, and `default_for_version`, built with `body`.
+pub fn functions(vis: &syn::Visibility, latest: &LitInt, body: TokenStream2) -> TokenStream2 {
+    let version = parameter();
+    let latest = LitInt::new(&format!("{}u32", latest.base10_digits()), latest.span());
+
+    quote! {
+        /// The newest version of the default values, the one used by `Default`.
+        #vis const DEFAULTS_VERSION: u32 = #latest;
+
+        /// Returns the default value of a version : each field gets its newest default value
+        /// whose version is at most `version`.
+        #vis fn default_for_version(#version: u32) -> Self {
+            #body
+        }
+    }
+}
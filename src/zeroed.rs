@@ -0,0 +1,169 @@
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, GenericArgument, PathArguments, Type};
+
+use crate::{
+    constants::{self, ZeroedPath},
+    default::DefaultValue,
+    types, Span2, TokenStream2,
+};
+
+/// The hidden associated constant generated on the types using the `zeroed` mode,
+/// marking them as valid when all their bytes are zero.
+pub const MARKER_IDENT: &str = "__BETTER_DEFAULT_ZERO_VALID";
+
+/// The primitive types, and their FFI aliases, for which the zero bit pattern is valid.
+const ZERO_VALID_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64", "bool", "char", "c_char", "c_schar", "c_uchar", "c_short", "c_ushort", "c_int",
+    "c_uint", "c_long", "c_ulong", "c_longlong", "c_ulonglong", "c_float", "c_double",
+    "PhantomData", "PhantomPinned", "MaybeUninit",
+];
+
+/// The std types which are known not to be valid when all their bytes are zero.
+const ZERO_INVALID_TYPES: &[&str] = &["NonNull", "Box", "Vec", "String", "Rc", "Arc"];
+
+/// Checks that the zero bit pattern is a valid value of `ty`.
+///
+/// Returns the types which can't be checked syntactically, like the nested structs,
+/// whose marker constant must be asserted in the generated code.
+pub fn check_zero_valid(ty: &Type) -> syn::Result<Vec<&Type>> {
+    let invalid = |kind: &str| {
+        Err(error!(
+            ty.span(),
+            "`{}` requires the fields to be valid when all their bytes are zero, which isn't the case of {}.",
+            constants::ZEROED_OPTION,
+            kind
+        ))
+    };
+
+    match types::strip(ty) {
+        Type::Array(array) => check_zero_valid(&array.elem),
+        Type::Tuple(tuple) => {
+            let mut nested = Vec::new();
+            for elem in &tuple.elems {
+                nested.extend(check_zero_valid(elem)?);
+            }
+
+            Ok(nested)
+        }
+        Type::Ptr(_) => Ok(Vec::new()),
+        Type::Reference(_) => invalid("references"),
+        Type::BareFn(_) => invalid("function pointers"),
+        Type::Path(_) => {
+            // the qualified paths, like `<T as Trait>::Output`, are checked by the marker assertion
+            let Some(segment) = types::last_segment(ty) else {
+                return Ok(vec![ty]);
+            };
+            let name = segment.ident.to_string();
+
+            if ZERO_VALID_TYPES.contains(&name.as_str()) {
+                Ok(Vec::new())
+            } else if name == "Option" {
+                if option_inner(segment).is_some_and(has_null_niche) {
+                    Ok(Vec::new())
+                } else {
+                    invalid("`Option`s of other types than references, function pointers, `Box`, `NonNull` and the non-zero integers")
+                }
+            } else if types::NON_ZERO_TYPES.contains(&name.as_str()) {
+                invalid("non-zero integers")
+            } else if ZERO_INVALID_TYPES.contains(&name.as_str()) {
+                invalid(&format!("`{}`", name))
+            } else {
+                Ok(vec![ty])
+            }
+        }
+        _ => invalid("these types"),
+    }
+}
+
+/// Whether `None` is guaranteed to be all zeros in an `Option` of `ty`.
+fn has_null_niche(ty: &Type) -> bool {
+    match types::strip(ty) {
+        Type::Reference(_) | Type::BareFn(_) => true,
+        Type::Path(_) => types::last_segment(ty).is_some_and(|s| {
+            let name = s.ident.to_string();
+            name == "NonNull" || name == "Box" || types::NON_ZERO_TYPES.contains(&name.as_str())
+        }),
+        _ => false,
+    }
+}
+
+fn option_inner(segment: &syn::PathSegment) -> Option<&Type> {
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Asserts that the nested types of the fields use the `zeroed` mode, through their marker constant.
+pub fn assertions(values: &[DefaultValue]) -> TokenStream2 {
+    values
+        .iter()
+        .flat_map(|value| {
+            let cfgs = &value.cfgs;
+            check_zero_valid(&value.ty)
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |ty| {
+                    let marker = format_ident!("{}", MARKER_IDENT, span = ty.span());
+                    quote_spanned! { ty.span() => #(#cfgs)* let _: () = <#ty>::#marker; }
+                })
+        })
+        .collect()
+}
+
+/// Builds the value from zeroed memory, then assigns the overridden fields.
+pub fn build(values: Vec<DefaultValue>) -> TokenStream2 {
+    let assertions = assertions(&values);
+
+    let binding = format_ident!("value", span = Span2::mixed_site());
+    let assignments: Vec<_> = values
+        .iter()
+        .filter(|value| value.is_override)
+        .map(|value| {
+            let member = value.member();
+            let field_value = &value.value;
+            let cfgs = &value.cfgs;
+
+            quote! { #(#cfgs)* #binding.#member = #field_value; }
+        })
+        .collect();
+
+    // SAFETY: the types of all the fields have been checked to be valid when all their bytes are zero
+    let zeroed = quote! { unsafe { #ZeroedPath() } };
+
+    if assignments.is_empty() {
+        return quote! {
+            {
+                #assertions
+                #zeroed
+            }
+        };
+    }
+
+    quote! {
+        {
+            #assertions
+            let mut #binding: Self = #zeroed;
+            #(#assignments)*
+            #binding
+        }
+    }
+}
+
+/// Generates the marker constant of a type using the `zeroed` mode.
+pub fn marker(ident: &syn::Ident, generics: &syn::Generics) -> TokenStream2 {
+    let marker = format_ident!("{}", MARKER_IDENT);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #ident #type_generics #where_clause {
+            #[doc(hidden)]
+            pub const #marker: () = ();
+        }
+    }
+}
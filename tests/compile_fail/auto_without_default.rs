@@ -0,0 +1,21 @@
+use better_default::Default;
+
+#[derive(Default)]
+struct Field {
+    #[default(auto)]
+    callback: fn(u32) -> u32,
+}
+
+#[derive(Default)]
+#[default(auto)]
+struct Container {
+    callbacks: [fn(); 2],
+}
+
+#[derive(Default)]
+struct Profiled {
+    #[default(profile = "test", auto)]
+    value: *const u8,
+}
+
+fn main() {}
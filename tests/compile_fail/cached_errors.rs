@@ -0,0 +1,23 @@
+use std::{cell::Cell, rc::Rc};
+
+use better_default::Default;
+
+#[derive(Default)]
+#[default(cached)]
+struct NotClone {
+    value: u32,
+}
+
+#[derive(Default, Clone)]
+#[default(cached)]
+struct NotSync {
+    value: Rc<Cell<u32>>,
+}
+
+#[derive(Default, Clone)]
+#[default(cached)]
+struct Generic<T: Default + Clone> {
+    value: T,
+}
+
+fn main() {}
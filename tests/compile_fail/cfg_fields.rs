@@ -0,0 +1,21 @@
+use better_default::Default;
+
+#[derive(Default)]
+#[default(#[cfg(all())] missing: 1)]
+struct UnknownWhenGuardHolds {
+    field: u32,
+}
+
+#[derive(Default)]
+#[default(#[cfg(any())] field: 1)]
+struct GuardDoesNotHold {
+    field: u32,
+}
+
+#[derive(Default)]
+#[default(#[allow(unused)] field: 1)]
+struct NotACfg {
+    field: u32,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use better_default::Default;
+
+#[derive(Default)]
+struct MissingFallback {
+    #[default(cfg(unix) = 1)]
+    field: u32,
+}
+
+#[derive(Default)]
+#[default(field: cfg(unix) = 1, cfg(windows) = 2)]
+struct MissingTopFallback {
+    field: u32,
+}
+
+#[derive(Default)]
+struct InvalidAlternative {
+    #[default(cfg(unix) = parse = "x", 0)]
+    field: u32,
+}
+
+#[derive(Default)]
+struct WrongActiveType {
+    #[default(cfg(all()) = "text", 0)]
+    field: u32,
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+use better_default::{Default, DefaultWith};
+
+#[derive(DefaultWith)]
+struct MissingContext {
+    field: u32,
+}
+
+#[derive(DefaultWith)]
+#[default_with(ctx: &u32)]
+struct UnknownContext {
+    #[default(context => *context)]
+    field: u32,
+}
+
+#[derive(Default)]
+struct ContextWithoutDerive {
+    #[default(ctx => *ctx)]
+    field: u32,
+}
+
+#[derive(DefaultWith)]
+#[default_with(ctx: &u32)]
+struct NestedWithValue {
+    #[default_with]
+    #[default(MissingContext { field: 1 })]
+    field: MissingContext,
+}
+
+fn main() {}
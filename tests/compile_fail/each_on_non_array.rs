@@ -0,0 +1,9 @@
+use better_default::Default;
+
+#[derive(Default)]
+struct Struct {
+    #[default(each = |i| i)]
+    field: Vec<usize>,
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use better_default::{Default, TryDefault};
+
+#[derive(Default)]
+enum Uncovered {
+    #[default(cfg(any()))]
+    Never,
+    Other,
+}
+
+#[derive(Default)]
+enum WhenWithoutFallback {
+    #[default(when = true)]
+    First,
+    #[default(when = false)]
+    Second,
+}
+
+#[derive(Default)]
+enum ProfiledConditional {
+    #[default(cfg(unix))]
+    #[default(profile = "prod"; 0: 1)]
+    Unix(u32),
+    #[default]
+    Other,
+}
+
+#[derive(Default)]
+#[default(cfg(unix))]
+struct OnStruct {
+    field: u32,
+}
+
+#[derive(TryDefault)]
+#[default(error = String)]
+enum TryConditional {
+    #[default(cfg(unix))]
+    Unix,
+    #[default]
+    Other,
+}
+
+#[derive(Default)]
+enum InvalidPredicate {
+    #[default(cfg(unix, windows))]
+    Unix,
+    #[default]
+    Other,
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use better_default::Default;
+
+#[derive(Default)]
+#[default(variants)]
+enum Mode {
+    #[default]
+    Fast(#[default(profile = "slow", 1)] u32),
+    Slow,
+}
+
+#[derive(Default)]
+enum Retry {
+    #[default]
+    Never,
+    Times(#[default(profile = "test", 3)] u32),
+}
+
+#[derive(Default, Clone)]
+#[default(cached)]
+enum Cached {
+    #[default]
+    Empty,
+    Ref(#[default(2)] u32),
+}
+
+#[derive(Default)]
+#[default(variants)]
+enum Versioned {
+    #[default]
+    Current(#[default(since = 1, 2)] u32),
+    ForVersion,
+}
+
+fn main() {}
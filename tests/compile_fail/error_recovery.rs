@@ -0,0 +1,42 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+struct NoDefault;
+
+// the errors of the attributes are the only errors, the implementation stays usable
+#[derive(Default)]
+#[default(profile = "prod"; retries: 5)]
+struct Struct {
+    #[default(NoDefault NoDefault)]
+    invalid: NoDefault,
+
+    #[default = 1]
+    pointer: *const u8,
+
+    retries: u32,
+}
+
+#[derive(Default)]
+#[default(auto, 0 = 1)]
+struct InvalidTop(*const u8, NoDefault);
+
+#[derive(Default)]
+enum MissingVariant {
+    Variant(NoDefault),
+    Unit,
+}
+
+#[derive(Default)]
+#[default(error = String)]
+struct TryDefaultOption {
+    #[default(profile = "prod", 1)]
+    field: u32,
+}
+
+fn main() {
+    let _: Struct = Struct::default_prod();
+    let _: InvalidTop = InvalidTop::default();
+    let _: MissingVariant = MissingVariant::default();
+    let _: TryDefaultOption = TryDefaultOption::default_prod();
+}
@@ -0,0 +1,29 @@
+use better_default::Default;
+
+#[derive(Default)]
+#[default(in_place)]
+struct Inner {
+    value: u32,
+}
+
+#[derive(Default)]
+struct MissingOption {
+    #[default(in_place)]
+    inner: Inner,
+}
+
+#[derive(Default)]
+#[default(in_place, inner: Inner { value: 1 })]
+struct WithValue {
+    #[default(in_place)]
+    inner: Inner,
+}
+
+#[derive(Default)]
+#[default(in_place)]
+enum Enum {
+    #[default]
+    A,
+}
+
+fn main() {}
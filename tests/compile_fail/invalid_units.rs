@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use better_default::Default;
+
+#[derive(Default)]
+struct Struct {
+    #[default(duration = "30 seconds")]
+    unknown_unit: Duration,
+
+    #[default(duration = "0.0000001ms")]
+    too_precise: Duration,
+
+    #[default(duration = "s")]
+    missing_number: Duration,
+
+    #[default(bytes = "1KiB")]
+    overflow: u8,
+
+    #[default(bytes = "0.5B")]
+    half_byte: u64,
+
+    #[default(bytes = "")]
+    empty: u64,
+}
+
+fn main() {}
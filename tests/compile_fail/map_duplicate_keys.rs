@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use better_default::Default;
+
+#[derive(Default)]
+struct Struct {
+    #[default({"a": 1, "b": 2, "a": 3})]
+    map: HashMap<String, u32>,
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+struct NoDefault;
+
+#[derive(Default)]
+struct MissingDefault {
+    #[default(1)]
+    count: u32,
+
+    field: NoDefault,
+
+    array: [NoDefault; 2],
+}
+
+#[derive(Default)]
+struct Modifiers {
+    #[default(some "x")]
+    some: Option<u32>,
+
+    #[default(into 5u64)]
+    into: u8,
+
+    #[default(boxed "x")]
+    boxed: Box<u32>,
+}
+
+#[derive(Default)]
+#[default(into_all)]
+struct IntoAll {
+    #[default(5u64)]
+    small: u8,
+}
+
+#[derive(Default)]
+struct Collections {
+    #[default([1, "a"])]
+    list: Vec<u32>,
+
+    #[default({"a": "b"})]
+    map: std::collections::HashMap<String, u32>,
+}
+
+#[derive(Default)]
+enum Enum {
+    #[default(0: "x")]
+    Variant(u32),
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use std::net::SocketAddr;
+
+use better_default::Default;
+
+#[derive(Default)]
+struct Struct {
+    #[default(parse = "127.0.0.1")]
+    addr: SocketAddr,
+
+    #[default(parse = "256")]
+    byte: u8,
+
+    #[default(parse = "yes")]
+    enabled: bool,
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+#[derive(Default)]
+struct Struct {
+    #[default(profile = "not valid", 0)]
+    field: u32,
+
+    #[default(profile = 5, 0)]
+    field2: u32,
+
+    #[default(profile = "prod" 0)]
+    field3: u32,
+}
+
+#[derive(Default)]
+#[default(profile = "prod", field: 1)]
+struct Struct2 {
+    field: u32,
+}
+
+#[derive(Default)]
+enum Enum {
+    #[default]
+    Variant1,
+
+    #[default(profile = "prod"; 0: 1)]
+    Variant2(u32),
+}
+
+fn main() {}
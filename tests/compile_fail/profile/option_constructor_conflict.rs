@@ -0,0 +1,17 @@
+use better_default::{Default, DefaultWith};
+
+#[derive(Default)]
+#[default(in_place)]
+struct Boxed {
+    #[default(profile = "boxed", 2)]
+    field: u32,
+}
+
+#[derive(Default, DefaultWith)]
+#[default_with(ctx: u32)]
+struct Context {
+    #[default(profile = "with", 2)]
+    field: u32,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+#[derive(Default)]
+#[default(profile = "prod"; field: 1)]
+#[default(profile = "prod"; field: 2)]
+struct Struct {
+    #[default(profile = "test", 0)]
+    #[default(profile = "test", 1)]
+    field: u32,
+
+    #[default(profile = "test", 0)]
+    #[default(profile = "prod", 1)]
+    #[default(0)]
+    #[default(1)]
+    field2: u32,
+}
+
+#[derive(Default)]
+#[default(profile = "prod"; field: 1)]
+struct Struct2 {
+    #[default(profile = "prod", 0)]
+    field: u32,
+}
+
+fn main() {}
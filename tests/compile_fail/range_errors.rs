@@ -0,0 +1,27 @@
+use better_default::Default;
+
+#[derive(Default)]
+struct Struct {
+    #[default(0, range = 1..=65535)]
+    port: u32,
+
+    #[default(10, range = 0..10)]
+    excluded_end: u8,
+
+    #[default(-0.5, range = 0.0..)]
+    negative: f32,
+
+    #[default([1, 2], range = 1..)]
+    array: Vec<u8>,
+
+    #[default(duration = "1s", range = 1..)]
+    duration: std::time::Duration,
+
+    #[default(9007199254740993, range = ..=9007199254740992)]
+    large: u64,
+
+    #[default(5, ranges = 1..)]
+    unknown: u8,
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+#[derive(Default)]
+struct Config {
+    #[default(Self::default().port + 1)]
+    port: u16,
+
+    #[default(<Self as Default>::default().retries)]
+    retries: u32,
+
+    #[default(Config::default().timeout)]
+    timeout: u32,
+}
+
+#[derive(Default)]
+enum Expr {
+    #[default]
+    Negate(Box<Expr>),
+    Literal(i32),
+}
+
+#[derive(Default)]
+enum Node {
+    #[default]
+    Pair {
+        left: Box<Self>,
+        right: (u32, std::rc::Rc<Node>),
+    },
+    Leaf,
+}
+
+#[derive(Default)]
+struct Link {
+    #[default(some boxed Default::default())]
+    next: Option<Box<Link>>,
+
+    #[default(Default::default())]
+    value: u32,
+}
+
+#[derive(Default)]
+#[default(allow(recursions))]
+struct UnknownLint {
+    field: u32,
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+// the lints are warnings, denied to be snapshotted
+#![deny(deprecated)]
+
+use std::collections::HashMap;
+
+use better_default::Default;
+
+#[derive(Default)]
+#[default(map: HashMap::new())]
+struct Config {
+    #[default(0)]
+    port: u16,
+
+    #[default(String::new())]
+    name: String,
+
+    #[default("".to_string())]
+    label: String,
+
+    #[default(false)]
+    flag: bool,
+
+    #[default(None)]
+    timeout: Option<u32>,
+
+    #[default(vec![])]
+    list: Vec<u8>,
+
+    #[default("")]
+    text: &'static str,
+
+    #[default(Default::default())]
+    ratio: f64,
+
+    map: HashMap<String, u32>,
+
+    #[default(3)]
+    #[default(profile = "prod", 3)]
+    #[default(profile = "test", 0)]
+    retries: u32,
+
+    // not redundant : the profile falls back to `5`
+    #[default(5)]
+    #[default(profile = "prod", 0)]
+    workers: u32,
+}
+
+#[derive(Default)]
+#[default(allow(redundant, redundant_profile))]
+struct Allowed {
+    #[default(0)]
+    #[default(profile = "prod", 0)]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+use better_default::{Default, TryDefault};
+
+#[derive(TryDefault)]
+struct MissingError {
+    field: u32,
+}
+
+#[derive(Default)]
+#[default(error = String)]
+struct FallibleDefault {
+    field: u32,
+}
+
+#[derive(TryDefault)]
+#[default(error = String, error = u32)]
+struct DuplicateOption {
+    field: u32,
+}
+
+#[derive(TryDefault)]
+#[default(errors = String)]
+struct UnknownOption {
+    field: u32,
+}
+
+#[derive(TryDefault)]
+enum Enum {
+    #[default(error = String)]
+    Variant,
+}
+
+fn main() {}
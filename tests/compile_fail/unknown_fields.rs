@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+#[derive(Default)]
+#[default(prot: 8080, hostname: "localhost".to_string())]
+struct Named {
+    port: u16,
+    host: String,
+}
+
+#[derive(Default)]
+#[default(0: 8080)]
+struct NamedWithIndex {
+    port: u16,
+}
+
+#[derive(Default)]
+#[default(2: 1)]
+struct Unnamed(u16, u16);
+
+#[derive(Default)]
+#[default(1: 1)]
+struct SingleUnnamed(u16);
+
+#[derive(Default)]
+#[default(port: 1)]
+struct UnnamedWithIdent(u16, u16);
+
+#[derive(Default)]
+enum Enum {
+    #[default(value: 1)]
+    Unit,
+}
+
+fn main() {}
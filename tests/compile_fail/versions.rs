@@ -0,0 +1,52 @@
+use better_default::Default;
+
+#[derive(Default)]
+struct Duplicated {
+    #[default(since = 2, 10)]
+    #[default(since = 2, 20)]
+    field: u32,
+}
+
+#[derive(Default)]
+struct OutOfOrder {
+    #[default(since = 3, 10)]
+    #[default(since = 2, 20)]
+    field: u32,
+}
+
+#[derive(Default)]
+struct VersionZero {
+    #[default(since = 0, 10)]
+    field: u32,
+}
+
+#[derive(Default)]
+struct Profiled {
+    #[default(profile = "prod", since = 2, 10)]
+    field: u32,
+}
+
+#[derive(Default)]
+struct Marker {
+    #[default(since = 2, auto)]
+    field: Option<u32>,
+}
+
+#[derive(Default)]
+enum NotDefault {
+    #[default]
+    A,
+    B {
+        #[default(since = 2, 10)]
+        field: u32,
+    },
+}
+
+#[derive(Default)]
+struct ProfileConflict {
+    #[default(profile = "for_version", 1)]
+    #[default(since = 2, 10)]
+    field: u32,
+}
+
+fn main() {}
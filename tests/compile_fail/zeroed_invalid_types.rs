@@ -0,0 +1,28 @@
+use std::num::NonZeroU8;
+
+use better_default::Default;
+
+struct NotZeroed {
+    value: u32,
+}
+
+#[derive(Default)]
+#[default(zeroed)]
+struct Struct {
+    name: &'static str,
+    id: NonZeroU8,
+    count: Option<u32>,
+    boxed: Box<u32>,
+    nested: NotZeroed,
+    #[default(1)]
+    valid: u32,
+}
+
+#[derive(Default)]
+#[default(zeroed)]
+enum Enum {
+    #[default]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use better_default::Default;
+
+struct NoDefault;
+
+// like the std derive, the variants which aren't the default one don't need their fields to implement `Default`
+#[derive(Default)]
+enum Enum {
+    #[default]
+    A,
+    B(NoDefault),
+    C { f: fn() },
+}
+
+fn main() {
+    assert!(matches!(Enum::default(), Enum::A));
+    let _ = (Enum::B(NoDefault), Enum::C { f: main });
+}
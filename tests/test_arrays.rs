@@ -0,0 +1,68 @@
+use better_default::Default;
+
+#[derive(Default)]
+#[allow(clippy::type_complexity)]
+struct Buffers {
+    samples: [u8; 64],
+    matrix: [[u16; 40]; 3],
+    tuple: (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8),
+    nested: ([u32; 33], (String, bool)),
+    unit: (),
+}
+
+#[test]
+fn test_large_arrays_and_tuples() {
+    let buffers = Buffers::default();
+
+    assert_eq!(buffers.samples, [0; 64]);
+    assert_eq!(buffers.matrix, [[0; 40]; 3]);
+    assert_eq!(buffers.tuple.0, 0);
+    assert_eq!(buffers.tuple.12, 0);
+    assert_eq!(buffers.nested, ([0; 33], (String::new(), false)));
+    assert_eq!(buffers.unit, ());
+}
+
+#[derive(Default, PartialEq, Debug)]
+struct Generic<T: Default, const N: usize> {
+    values: [T; N],
+}
+
+#[test]
+fn test_generic_length() {
+    assert_eq!(Generic::<String, 40>::default().values, [(); 40].map(|_| String::new()));
+    assert_eq!(Generic::<u8, 0>::default().values, []);
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(table: each = |i| i as u8 * 2)]
+struct Indexed {
+    #[default(each = |i| i * i)]
+    squares: [usize; 50],
+
+    table: [u8; 4],
+}
+
+#[test]
+fn test_each() {
+    let indexed = Indexed::default();
+
+    assert_eq!(indexed.squares[7], 49);
+    assert_eq!(indexed.squares[49], 2401);
+    assert_eq!(indexed.table, [0, 2, 4, 6]);
+}
+
+#[derive(PartialEq, Debug)]
+struct NoDefault;
+
+#[derive(Default, PartialEq, Debug)]
+struct Empty {
+    // core implements `Default` for the empty arrays of any type
+    values: [NoDefault; 0],
+    short: [u8; 32],
+}
+
+#[test]
+fn test_empty_array() {
+    assert_eq!(Empty::default().values, []);
+    assert_eq!(Empty::default().short, [0; 32]);
+}
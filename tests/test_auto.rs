@@ -0,0 +1,78 @@
+#![allow(dead_code)]
+
+use std::{
+    num::{NonZero, NonZeroU32, NonZeroUsize},
+    ptr::NonNull,
+};
+
+use better_default::Default;
+
+#[derive(Default, Debug)]
+struct Handles {
+    #[default(auto)]
+    id: NonZeroU32,
+
+    #[default(auto)]
+    raw: *const u8,
+
+    #[default(auto)]
+    raw_mut: *mut u8,
+
+    #[default(auto)]
+    non_null: NonNull<u64>,
+
+    #[default(5)]
+    count: u32,
+}
+
+#[test]
+fn test_field_auto() {
+    let handles = Handles::default();
+
+    assert_eq!(handles.id.get(), 1);
+    assert!(handles.raw.is_null());
+    assert!(handles.raw_mut.is_null());
+    assert_eq!(handles.non_null, NonNull::dangling());
+    assert_eq!(handles.count, 5);
+}
+
+#[derive(Default, Debug)]
+#[default(auto, label: "label")]
+struct Static {
+    name: &'static str,
+    label: &'static str,
+    bytes: &'static [u8],
+    parent: Option<&'static Static>,
+    sizes: [NonZeroUsize; 40],
+    pair: (NonZero<u8>, *const ()),
+    count: u32,
+}
+
+#[test]
+fn test_container_auto() {
+    let value = Static::default();
+
+    assert_eq!(value.name, "");
+    assert_eq!(value.label, "label");
+    assert!(value.bytes.is_empty());
+    assert!(value.parent.is_none());
+    assert_eq!(value.sizes, [NonZeroUsize::MIN; 40]);
+    assert_eq!(value.pair.0.get(), 1);
+    assert!(value.pair.1.is_null());
+    assert_eq!(value.count, 0);
+}
+
+#[derive(Default, Debug)]
+#[default(auto)]
+enum Node {
+    #[default]
+    Leaf {
+        data: *const u8,
+    },
+    Branch,
+}
+
+#[test]
+fn test_enum_auto() {
+    assert!(matches!(Node::default(), Node::Leaf { data } if data.is_null()));
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "std")]
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use better_default::Default;
+
+static COMPUTED: AtomicUsize = AtomicUsize::new(0);
+
+fn expensive_table() -> HashMap<String, u32> {
+    COMPUTED.fetch_add(1, Ordering::SeqCst);
+
+    (0..100).map(|i| (i.to_string(), i)).collect()
+}
+
+#[derive(Default, Clone, PartialEq, Debug)]
+#[default(cached, name: "config".to_string())]
+struct Config {
+    #[default(expensive_table())]
+    table: HashMap<String, u32>,
+    name: String,
+}
+
+#[test]
+fn test_cached() {
+    let first = Config::default();
+    let second = Config::default();
+    let reference = Config::default_ref();
+
+    assert_eq!(first, second);
+    assert_eq!(&first, reference);
+    assert!(std::ptr::eq(reference, Config::default_ref()));
+    assert_eq!(first.table["42"], 42);
+    assert_eq!(first.name, "config");
+    assert_eq!(COMPUTED.load(Ordering::SeqCst), 1);
+}
+
+#[derive(Default, Clone, PartialEq, Debug)]
+#[default(cached)]
+enum Mode {
+    Fast(u32),
+    #[default(level: 3)]
+    Slow { level: u8 },
+}
+
+#[test]
+fn test_cached_enum() {
+    assert_eq!(Mode::default(), Mode::Slow { level: 3 });
+    assert_eq!(Mode::default_ref(), &Mode::Slow { level: 3 });
+}
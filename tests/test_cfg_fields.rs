@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+// for `default_boxed` without the `std` feature
+extern crate alloc;
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(#[cfg(not(test))] tls_cert: "cert".to_string(), #[cfg(test)] port: 443)]
+struct Config {
+    #[cfg(not(test))]
+    tls_cert: String,
+
+    #[cfg(test)]
+    port: u16,
+
+    #[cfg(test)]
+    #[default(3)]
+    retries: u32,
+}
+
+#[test]
+fn test_cfg_fields() {
+    assert_eq!(
+        Config::default(),
+        Config {
+            port: 443,
+            retries: 3
+        }
+    );
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(trace_panics)]
+struct Tuple(#[cfg(test)] u32, #[default(2)] u8);
+
+#[test]
+fn test_cfg_unnamed_fields() {
+    assert_eq!(Tuple::default(), Tuple(0, 2));
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(in_place)]
+struct InPlace {
+    #[cfg(test)]
+    #[default(5)]
+    value: u32,
+
+    #[cfg(test)]
+    other: u32,
+}
+
+#[test]
+fn test_cfg_fields_in_place() {
+    let boxed = InPlace::default_boxed();
+    assert_eq!(*boxed, InPlace { value: 5, other: 0 });
+}
@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+struct Logging {
+    #[default(cfg(debug_assertions) = 3, 1)]
+    verbosity: u8,
+
+    #[default(cfg(target_os = "linux") = "/var/log".to_string(), cfg(unix) = "/tmp".to_string(), "logs".to_string())]
+    directory: String,
+
+    #[default(cfg(any()) = 100, 10, range = 1..=100)]
+    max_files: u32,
+
+    #[default(cfg(any()) = some 1, some 2)]
+    rotation: Option<u32>,
+}
+
+#[test]
+fn test_cfg_field_values() {
+    let logging = Logging::default();
+
+    assert_eq!(logging.verbosity, if cfg!(debug_assertions) { 3 } else { 1 });
+    assert_eq!(logging.max_files, 10);
+    assert_eq!(logging.rotation, Some(2));
+
+    // the first matching predicate is used
+    if cfg!(target_os = "linux") {
+        assert_eq!(logging.directory, "/var/log");
+    } else if cfg!(unix) {
+        assert_eq!(logging.directory, "/tmp");
+    } else {
+        assert_eq!(logging.directory, "logs");
+    }
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(port: cfg(all()) = 8080, 80, host: "localhost")]
+struct Server {
+    port: u16,
+    host: &'static str,
+}
+
+#[test]
+fn test_cfg_top_values() {
+    assert_eq!(
+        Server::default(),
+        Server {
+            port: 8080,
+            host: "localhost"
+        }
+    );
+}
+
+#[derive(Default, PartialEq, Debug)]
+struct OnlyActiveBranch {
+    // the inactive value isn't type-checked, so it can use the items of a disabled feature
+    #[default(cfg(any()) = missing::Type::new(), 5)]
+    value: u32,
+}
+
+#[test]
+fn test_inactive_branch_is_not_type_checked() {
+    assert_eq!(OnlyActiveBranch::default().value, 5);
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Mode {
+    #[default]
+    Run {
+        #[default(cfg(test) = 1, 2)]
+        threads: u32,
+    },
+}
+
+#[test]
+fn test_cfg_variant_values() {
+    assert_eq!(Mode::default(), Mode::Run { threads: 1 });
+}
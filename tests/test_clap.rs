@@ -0,0 +1,133 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+use clap::{CommandFactory, Parser};
+
+#[derive(clap::ValueEnum, Clone, Default, PartialEq, Debug)]
+enum Mode {
+    #[default]
+    Fast,
+    Safe,
+}
+
+#[better_default::clap_defaults]
+#[derive(Parser, Default, PartialEq, Debug)]
+#[default(workers: 4)]
+struct Cli {
+    #[arg(long)]
+    #[default("localhost".to_string())]
+    host: String,
+
+    #[arg(long)]
+    #[default(8080)]
+    port: u16,
+
+    #[arg(long)]
+    workers: u32,
+
+    #[arg(long)]
+    retries: u32,
+
+    #[arg(long, default_value_t = 3)]
+    timeout: u32,
+
+    #[arg(long)]
+    #[default(Some(1))]
+    level: Option<u8>,
+
+    #[arg(long)]
+    name: Option<String>,
+
+    #[arg(long, value_enum)]
+    mode: Mode,
+
+    #[arg(long)]
+    #[default(vec![1, 2])]
+    ids: Vec<u32>,
+}
+
+#[test]
+fn test_empty_args_match_default() {
+    let cli = Cli::try_parse_from(["app"]).unwrap();
+
+    assert_eq!(
+        cli,
+        Cli {
+            timeout: 3,
+            ..Cli::default()
+        }
+    );
+}
+
+#[test]
+fn test_args_still_override_default() {
+    let cli = Cli::try_parse_from(["app", "--port", "80", "--host", "example.com"]).unwrap();
+
+    assert_eq!(cli.port, 80);
+    assert_eq!(cli.host, "example.com");
+    assert_eq!(cli.workers, 4);
+}
+
+#[test]
+fn test_help_shows_default() {
+    let help = Cli::command().render_help().to_string();
+
+    assert!(help.contains("[default: localhost]"));
+    assert!(help.contains("[default: 8080]"));
+    assert!(help.contains("[default: 4]"));
+    assert!(help.contains("[default: 0]"));
+    assert!(help.contains("[default: 1]"));
+    assert!(help.contains("[default: fast]"));
+}
+
+#[better_default::clap_defaults]
+#[derive(Parser, Default, PartialEq, Debug)]
+#[default(input: "input.txt".to_string())]
+struct PositionalCli {
+    input: String,
+
+    #[default(2)]
+    count: u8,
+}
+
+#[test]
+fn test_positional_args() {
+    let cli = PositionalCli::try_parse_from(["app"]).unwrap();
+
+    assert_eq!(cli, PositionalCli::default());
+}
+
+#[better_default::clap_defaults]
+#[derive(Parser, Default, PartialEq, Debug)]
+struct PathCli {
+    // the default value of `PathBuf` is the empty path
+    #[arg(long)]
+    config: std::path::PathBuf,
+
+    // the values are given to clap as `OsString`
+    #[arg(long)]
+    #[default("out".into())]
+    output: std::path::PathBuf,
+
+    #[arg(long)]
+    #[default(vec!["a".into(), "b".into()])]
+    includes: Vec<std::path::PathBuf>,
+
+    #[arg(long)]
+    #[default(some "cache".into())]
+    cache: Option<std::path::PathBuf>,
+}
+
+#[test]
+fn test_path_args() {
+    assert_eq!(PathCli::try_parse_from(["app"]).unwrap(), PathCli::default());
+
+    let cli = PathCli::try_parse_from(["app", "--config", "app.toml"]).unwrap();
+    assert_eq!(
+        cli,
+        PathCli {
+            config: "app.toml".into(),
+            ..PathCli::default()
+        }
+    );
+}
@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(allow(redundant), tags: ["a", "b"], limits: {"soft": 10, "hard": 20})]
+struct Config {
+    #[default([1, 2, 3])]
+    numbers: Vec<u64>,
+
+    tags: HashSet<String>,
+
+    limits: HashMap<String, u32>,
+
+    #[default({1: [1u8, 2], 2: []})]
+    groups: BTreeMap<u8, Vec<u8>>,
+
+    #[default([-1.5, 2.0])]
+    queue: VecDeque<f32>,
+
+    #[default([1, 2])]
+    array: [u8; 2],
+
+    #[default(some ["x"])]
+    optional: Option<Vec<String>>,
+
+    #[default(into [1, 2])]
+    converted: Vec<u8>,
+
+    #[default({ let value = 4; value * 2 })]
+    block: u32,
+
+    #[default([])]
+    empty: Vec<String>,
+}
+
+#[test]
+fn test_collection_literals() {
+    let config = Config::default();
+
+    assert_eq!(config.numbers, [1, 2, 3]);
+    assert_eq!(config.tags, HashSet::from(["a".to_string(), "b".to_string()]));
+    assert_eq!(config.limits["soft"], 10);
+    assert_eq!(config.limits["hard"], 20);
+    assert_eq!(config.groups, BTreeMap::from([(1, vec![1, 2]), (2, vec![])]));
+    assert_eq!(config.queue, [-1.5, 2.0]);
+    assert_eq!(config.array, [1, 2]);
+    assert_eq!(config.optional, Some(vec!["x".to_string()]));
+    assert_eq!(config.converted, [1, 2]);
+    assert_eq!(config.block, 8);
+    assert!(config.empty.is_empty());
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(into_all)]
+struct IntoAll {
+    #[default(["a"])]
+    names: Vec<String>,
+
+    #[default("b")]
+    name: String,
+}
+
+#[test]
+fn test_collection_into_all() {
+    assert_eq!(
+        IntoAll::default(),
+        IntoAll {
+            names: vec!["a".to_string()],
+            name: "b".to_string()
+        }
+    );
+}
+
+type Rgb = [u8; 3];
+
+#[derive(Default, PartialEq, Debug)]
+struct Theme {
+    // the aliases of array types stay arrays
+    #[default([255, 0, 0])]
+    color: Rgb,
+
+    #[default([1, 2])]
+    sizes: Box<[u32]>,
+}
+
+#[test]
+fn test_array_alias() {
+    assert_eq!(
+        Theme::default(),
+        Theme {
+            color: [255, 0, 0],
+            sizes: Box::new([1, 2])
+        }
+    );
+}
+
+#[derive(PartialEq, Debug)]
+struct Total(u32);
+
+impl FromIterator<u32> for Total {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        Self(iter.into_iter().sum())
+    }
+}
+
+type Names = Vec<String>;
+
+#[derive(Default, PartialEq, Debug)]
+struct Custom {
+    #[default([1, 2, 3])]
+    total: Total,
+
+    #[default(["a", "b"])]
+    names: Names,
+}
+
+#[test]
+fn test_any_from_iterator() {
+    assert_eq!(
+        Custom::default(),
+        Custom {
+            total: Total(6),
+            names: vec!["a".to_string(), "b".to_string()]
+        }
+    );
+}
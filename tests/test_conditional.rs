@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(variants)]
+enum Backend {
+    #[default(cfg(target_os = "linux"))]
+    Epoll,
+
+    #[default(cfg(windows))]
+    Iocp,
+
+    #[default]
+    Poll,
+}
+
+#[test]
+fn test_cfg_default() {
+    if cfg!(target_os = "linux") {
+        assert_eq!(Backend::default(), Backend::Epoll);
+    } else if cfg!(windows) {
+        assert_eq!(Backend::default(), Backend::Iocp);
+    } else {
+        assert_eq!(Backend::default(), Backend::Poll);
+    }
+}
+
+#[test]
+fn test_conditional_variant_constructors() {
+    assert_eq!(Backend::default_epoll(), Backend::Epoll);
+    assert_eq!(Backend::default_iocp(), Backend::Iocp);
+    assert_eq!(Backend::default_poll(), Backend::Poll);
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Platform {
+    #[default(cfg(unix); 0: "unix")]
+    Unix(&'static str),
+
+    #[default(cfg(not(unix)))]
+    Other {
+        #[default("other")]
+        name: &'static str,
+    },
+}
+
+#[test]
+fn test_exhaustive_cfg_without_fallback() {
+    if cfg!(unix) {
+        assert_eq!(Platform::default(), Platform::Unix("unix"));
+    } else {
+        assert_eq!(Platform::default(), Platform::Other { name: "other" });
+    }
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Overlapping {
+    #[default(cfg(any(unix, windows)))]
+    First,
+
+    #[default(cfg(all()))]
+    Second,
+}
+
+#[test]
+fn test_first_matching_cfg() {
+    if cfg!(any(unix, windows)) {
+        assert_eq!(Overlapping::default(), Overlapping::First);
+    } else {
+        assert_eq!(Overlapping::default(), Overlapping::Second);
+    }
+}
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default, PartialEq, Debug)]
+enum Level {
+    #[default(when = VERBOSE.load(Ordering::Relaxed))]
+    Debug,
+
+    #[default(when = QUIET.load(Ordering::Relaxed))]
+    Error,
+
+    #[default]
+    Info,
+}
+
+#[test]
+fn test_when_default() {
+    assert_eq!(Level::default(), Level::Info);
+
+    QUIET.store(true, Ordering::Relaxed);
+    assert_eq!(Level::default(), Level::Error);
+
+    // the conditions are checked in declaration order
+    VERBOSE.store(true, Ordering::Relaxed);
+    assert_eq!(Level::default(), Level::Debug);
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Mixed {
+    #[default(when = false)]
+    Never,
+
+    #[default(cfg(all()))]
+    Always(#[default(7)] u32),
+
+    #[default(when = true)]
+    Shadowed,
+
+    #[default]
+    Fallback,
+}
+
+#[test]
+fn test_when_and_cfg_in_declaration_order() {
+    assert_eq!(Mixed::default(), Mixed::Always(7));
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Keywords {
+    // fields named like the conditions aren't conditions
+    #[default(when: 5, cfg: 2)]
+    A { when: u32, cfg: u32 },
+    B,
+}
+
+#[test]
+fn test_fields_named_like_conditions() {
+    assert_eq!(Keywords::default(), Keywords::A { when: 5, cfg: 2 });
+}
@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+
+use better_default::{Default, DefaultWith};
+
+struct AppContext {
+    cpus: u32,
+    data_dir: &'static str,
+}
+
+const CONTEXT: AppContext = AppContext {
+    cpus: 4,
+    data_dir: "/var/lib/app",
+};
+
+#[derive(Default, DefaultWith, PartialEq, Debug)]
+#[default_with(ctx: &AppContext)]
+struct Storage {
+    #[default(ctx => format!("{}/storage", ctx.data_dir))]
+    path: String,
+
+    #[default(64)]
+    cache_size: u32,
+}
+
+#[derive(Default, DefaultWith, PartialEq, Debug)]
+#[default_with(ctx: &AppContext)]
+#[default(queue: ctx => ctx.cpus * 100)]
+struct Config {
+    #[default(ctx => ctx.cpus * 2)]
+    workers: u32,
+
+    queue: u32,
+
+    #[default(3)]
+    retries: u32,
+
+    #[default_with]
+    storage: Storage,
+
+    name: String,
+}
+
+#[test]
+fn test_default_with() {
+    let config = Config::default_with(&CONTEXT);
+    let expected = Config {
+        workers: 8,
+        queue: 400,
+        retries: 3,
+        storage: Storage {
+            path: "/var/lib/app/storage".to_string(),
+            cache_size: 64,
+        },
+        name: String::new(),
+    };
+
+    assert_eq!(config, expected);
+}
+
+#[test]
+fn test_default_ignores_context() {
+    let expected = Config {
+        workers: 0,
+        queue: 0,
+        retries: 3,
+        storage: Storage {
+            path: String::new(),
+            cache_size: 64,
+        },
+        name: String::new(),
+    };
+
+    assert_eq!(Config::default(), expected);
+}
+
+#[derive(DefaultWith, PartialEq, Debug)]
+#[default_with(cpus: u32)]
+enum Mode {
+    Single,
+
+    #[default]
+    Parallel(#[default(cpus => cpus as usize)] usize),
+}
+
+#[test]
+fn test_default_with_enum() {
+    assert_eq!(Mode::default_with(6), Mode::Parallel(6));
+}
@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+// for `default_boxed` without the `std` feature
+extern crate alloc;
+
+use std::mem::MaybeUninit;
+
+use better_default::Default;
+
+// large enough to overflow the stack if built on it, except under Miri where it would be too slow
+const BUFFER_LEN: usize = if cfg!(miri) { 256 } else { 4 << 20 };
+const GRID_LEN: usize = if cfg!(miri) { 16 } else { 1024 };
+
+#[derive(Default, Debug, PartialEq)]
+#[default(in_place, name: "state".to_string())]
+struct State {
+    buffer: [u8; BUFFER_LEN],
+    #[default(each = |i| i as u32)]
+    indices: [u32; 16],
+    name: String,
+    grid: [[u16; GRID_LEN]; GRID_LEN],
+    #[default(in_place)]
+    inner: Inner,
+}
+
+#[derive(Default, Debug, PartialEq)]
+#[default(in_place)]
+struct Inner {
+    #[default(vec![1, 2, 3])]
+    values: Vec<u32>,
+    samples: [f32; 64],
+}
+
+#[test]
+fn test_default_boxed() {
+    let state = State::default_boxed();
+
+    assert!(state.buffer.iter().all(|&b| b == 0));
+    assert_eq!(state.indices[15], 15);
+    assert_eq!(state.name, "state");
+    assert!(state.grid.iter().flatten().all(|&v| v == 0));
+    assert_eq!(state.inner.values, [1, 2, 3]);
+    assert!(state.inner.samples.iter().all(|&v| v == 0.0));
+}
+
+#[test]
+fn test_default_in_place() {
+    let mut slot = MaybeUninit::<Inner>::uninit();
+
+    let inner = unsafe {
+        Inner::default_in_place(slot.as_mut_ptr());
+        slot.assume_init()
+    };
+
+    assert_eq!(inner, Inner::default());
+}
+
+#[derive(Default, Debug, PartialEq)]
+#[default(in_place, zeroed)]
+#[repr(C)]
+struct Zeroed(u64, #[default(3)] u8, [u16; 1000]);
+
+#[derive(Default, Debug, PartialEq)]
+#[default(in_place)]
+struct Generic<T: Default, const N: usize> {
+    values: [T; N],
+    unit: (),
+}
+
+#[test]
+fn test_zeroed_and_generics() {
+    assert_eq!(*Zeroed::default_boxed(), Zeroed(0, 3, [0; 1000]));
+    assert_eq!(*Generic::<String, 40>::default_boxed(), Generic::default());
+}
@@ -0,0 +1,119 @@
+// for the `boxed`, `arc` and `rc` modifiers without the `std` feature
+extern crate alloc;
+
+use std::{rc::Rc, sync::Arc};
+
+use better_default::Default;
+
+trait Handler {
+    fn id(&self) -> u32;
+}
+
+struct Impl(u32);
+
+impl Handler for Impl {
+    fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+fn some(value: u32) -> Option<u32> {
+    Some(value + 1)
+}
+
+#[derive(Default)]
+#[default(host: into "localhost", shared: arc vec![1, 2])]
+struct Server {
+    host: String,
+
+    #[default(some 5)]
+    port: Option<u16>,
+
+    #[default(boxed Impl(3))]
+    handler: Box<dyn Handler>,
+
+    shared: Arc<Vec<u8>>,
+
+    #[default(rc into "local")]
+    local: Rc<String>,
+
+    #[default(some into "name")]
+    name: Option<String>,
+
+    #[default(some(1))]
+    not_a_modifier: Option<u32>,
+
+    #[default(some i32::MIN)]
+    negative: Option<i32>,
+}
+
+#[test]
+fn test_modifiers() {
+    let server = Server::default();
+
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.port, Some(5));
+    assert_eq!(server.handler.id(), 3);
+    assert_eq!(*server.shared, [1, 2]);
+    assert_eq!(*server.local, "local");
+    assert_eq!(server.name.as_deref(), Some("name"));
+    assert_eq!(server.not_a_modifier, Some(2));
+    assert_eq!(server.negative, Some(i32::MIN));
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(into_all, user: "admin")]
+struct Credentials {
+    user: String,
+
+    #[default("secret")]
+    password: String,
+
+    #[default(into "token")]
+    token: Box<str>,
+
+    #[default(8080u16)]
+    port: u32,
+
+    #[default(each = |i| i as u8)]
+    salt: [u8; 4],
+
+    retries: u8,
+}
+
+#[test]
+fn test_into_all() {
+    let credentials = Credentials::default();
+
+    assert_eq!(credentials.user, "admin");
+    assert_eq!(credentials.password, "secret");
+    assert_eq!(&*credentials.token, "token");
+    assert_eq!(credentials.port, 8080);
+    assert_eq!(credentials.salt, [0, 1, 2, 3]);
+    assert_eq!(credentials.retries, 0);
+}
+
+mod binary {
+    use better_default::Default;
+
+    #[allow(non_upper_case_globals)]
+    const some: i32 = 5;
+
+    #[derive(Default)]
+    struct Offsets {
+        // a binary expression, not the `some` modifier
+        #[default(some - 1)]
+        difference: i32,
+
+        #[default(some & 4)]
+        mask: i32,
+    }
+
+    #[test]
+    fn test_binary_expression() {
+        let offsets = Offsets::default();
+
+        assert_eq!(offsets.difference, 4);
+        assert_eq!(offsets.mask, 4);
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6},
+    path::PathBuf,
+    time::Duration,
+};
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(ip: parse = "::1")]
+struct Network {
+    #[default(parse = "127.0.0.1:8080")]
+    addr: SocketAddr,
+
+    #[default(parse = "[fe80::1%2]:443")]
+    addr_v6: SocketAddrV6,
+
+    ip: IpAddr,
+
+    #[default(parse = "10.0.0.1")]
+    ipv4: Ipv4Addr,
+
+    #[default(parse = "2001:db8::ff")]
+    ipv6: Ipv6Addr,
+
+    #[default(parse = "-12")]
+    offset: i64,
+
+    #[default(parse = "255")]
+    max: u8,
+
+    #[default(parse = "1.5e3")]
+    scale: f32,
+
+    #[default(parse = "-inf")]
+    floor: f64,
+
+    #[default(parse = "true")]
+    enabled: bool,
+
+    #[default(parse = "λ")]
+    symbol: char,
+
+    #[default(parse = "/var/log")]
+    path: PathBuf,
+}
+
+#[test]
+fn test_parse() {
+    let network = Network::default();
+
+    assert_eq!(network.addr, "127.0.0.1:8080".parse().unwrap());
+    assert_eq!(network.addr_v6, "[fe80::1%2]:443".parse().unwrap());
+    assert_eq!(network.addr_v6.scope_id(), 2);
+    assert_eq!(network.ip, IpAddr::V6(Ipv6Addr::LOCALHOST));
+    assert_eq!(network.ipv4, Ipv4Addr::new(10, 0, 0, 1));
+    assert_eq!(network.ipv6, "2001:db8::ff".parse::<Ipv6Addr>().unwrap());
+    assert_eq!(network.offset, -12);
+    assert_eq!(network.max, 255);
+    assert_eq!(network.scale, 1500.0);
+    assert_eq!(network.floor, f64::NEG_INFINITY);
+    assert!(network.enabled);
+    assert_eq!(network.symbol, 'λ');
+    assert_eq!(network.path, PathBuf::from("/var/log"));
+}
+
+#[derive(Default)]
+#[allow(dead_code)]
+struct Invalid {
+    #[default(parse = "not a duration")]
+    timeout: Wrapper,
+}
+
+#[derive(PartialEq, Debug)]
+struct Wrapper(Duration);
+
+impl std::str::FromStr for Wrapper {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(|secs| Self(Duration::from_secs(secs)))
+    }
+}
+
+#[test]
+#[should_panic(expected = "failed to parse the default value of the field `timeout`")]
+fn test_parse_from_str_panics() {
+    Invalid::default();
+}
@@ -0,0 +1,78 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(profile = "prod"; workers: 64)]
+struct Config {
+    #[default(5)]
+    #[default(profile = "prod", 50)]
+    #[default(profile = "test", 1)]
+    retries: u32,
+
+    #[default(4)]
+    workers: u32,
+
+    #[default(profile = "test", "memory".to_string())]
+    storage: String,
+}
+
+#[test]
+fn test_unqualified_default() {
+    assert_eq!(
+        Config::default(),
+        Config {
+            retries: 5,
+            workers: 4,
+            storage: String::new(),
+        }
+    );
+}
+
+#[test]
+fn test_profile_default() {
+    assert_eq!(
+        Config::default_prod(),
+        Config {
+            retries: 50,
+            workers: 64,
+            storage: String::new(),
+        }
+    );
+
+    assert_eq!(
+        Config::default_test(),
+        Config {
+            retries: 1,
+            workers: 4,
+            storage: "memory".to_string(),
+        }
+    );
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(0: 1)]
+#[default(profile = "prod"; 0: 10, 1: 20)]
+struct Tuple(u32, u32);
+
+#[test]
+fn test_profile_top_attribute_unnamed() {
+    assert_eq!(Tuple::default(), Tuple(1, 0));
+    assert_eq!(Tuple::default_prod(), Tuple(10, 20));
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Enum {
+    Variant1,
+
+    #[default]
+    #[default(profile = "prod"; 1: "prod".to_string())]
+    Variant2(#[default(profile = "test", 3)] u32, String),
+}
+
+#[test]
+fn test_profile_enum() {
+    assert_eq!(Enum::default(), Enum::Variant2(0, String::new()));
+    assert_eq!(Enum::default_prod(), Enum::Variant2(0, "prod".to_string()));
+    assert_eq!(Enum::default_test(), Enum::Variant2(3, String::new()));
+}
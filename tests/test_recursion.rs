@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use better_default::Default;
+
+static LINKS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default, PartialEq, Debug)]
+#[default(allow(recursion))]
+struct Chain {
+    #[default(if LINKS.fetch_add(1, Ordering::Relaxed) < 2 { Some(Box::new(Self::default())) } else { None })]
+    next: Option<Box<Chain>>,
+}
+
+#[test]
+fn test_allowed_recursion() {
+    let chain = Chain::default();
+
+    assert_eq!(chain.next.unwrap().next.unwrap().next, None);
+}
+
+#[derive(Default, PartialEq, Debug)]
+struct Tree {
+    children: Vec<Tree>,
+    parent: Option<Box<Tree>>,
+
+    #[default(some Box::new(Tree { children: Vec::new(), parent: None, sibling: None }))]
+    sibling: Option<Box<Tree>>,
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Expr {
+    #[default(0: Box::new(Expr::Literal(1)))]
+    Negate(Box<Expr>),
+    Literal(i32),
+}
+
+#[test]
+fn test_non_recursive() {
+    let tree = Tree::default();
+    assert!(tree.children.is_empty());
+    assert_eq!(tree.parent, None);
+    assert!(tree.sibling.is_some());
+
+    assert_eq!(Expr::default(), Expr::Negate(Box::new(Expr::Literal(1))));
+}
@@ -98,6 +98,7 @@ fn test_unnamed_fields_top_field_attrs() {
 struct Unit;
 
 #[test]
+#[allow(clippy::default_constructed_unit_structs)]
 fn test_unit() {
     let default = Unit::default();
     assert_eq!(Unit, default);
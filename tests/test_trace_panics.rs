@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+use std::process::Command;
+
+use better_default::Default;
+
+fn parse_port(port: &str) -> u16 {
+    port.parse().unwrap()
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(trace_panics, field2: 2)]
+struct Config {
+    #[default(1)]
+    field1: u32,
+
+    field2: u32,
+
+    #[default(parse_port("80a"))]
+    field3: u16,
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(trace_panics)]
+struct Valid(#[default(parse_port("80"))] u16, String);
+
+#[test]
+fn test_trace_panics_build() {
+    assert_eq!(Valid::default(), Valid(80, String::new()));
+}
+
+#[test]
+#[ignore = "panics on purpose, run by `test_trace_panics_report`"]
+fn panicking_default() {
+    Config::default();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_trace_panics_report() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["panicking_default", "--exact", "--ignored", "--nocapture"])
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("while computing default of Config::field3"));
+    assert!(!stderr.contains("Config::field2"));
+}
@@ -0,0 +1,97 @@
+#![allow(dead_code)]
+
+use std::num::ParseIntError;
+
+use better_default::TryDefault;
+
+#[derive(Debug, PartialEq)]
+enum ConfigError {
+    Parse(ParseIntError),
+    Field(&'static str, Box<ConfigError>),
+}
+
+impl From<ParseIntError> for ConfigError {
+    fn from(error: ParseIntError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl ConfigError {
+    fn field(name: &'static str, error: ConfigError) -> Self {
+        Self::Field(name, Box::new(error))
+    }
+}
+
+const PORT: &str = "8080";
+const BAD_PORT: &str = "80a";
+
+#[derive(TryDefault, PartialEq, Debug)]
+#[default(error = ConfigError)]
+struct Config {
+    #[default(PORT.parse()?)]
+    port: u16,
+
+    #[default("localhost".to_string())]
+    host: String,
+
+    retries: u32,
+}
+
+#[test]
+fn test_try_default() {
+    let expected = Config {
+        port: 8080,
+        host: "localhost".to_string(),
+        retries: 0,
+    };
+
+    assert_eq!(Config::try_default(), Ok(expected));
+}
+
+#[derive(TryDefault, PartialEq, Debug)]
+#[default(error = ConfigError, field_error = ConfigError::field, timeout: "30".parse()?)]
+struct BadConfig {
+    #[default(BAD_PORT.parse()?)]
+    port: u16,
+
+    timeout: u32,
+}
+
+#[test]
+fn test_try_default_field_error() {
+    let Err(ConfigError::Field(name, error)) = BadConfig::try_default() else {
+        panic!("the error should be wrapped with the field name");
+    };
+
+    assert_eq!(name, "port");
+    assert!(matches!(*error, ConfigError::Parse(_)));
+}
+
+#[derive(TryDefault, PartialEq, Debug)]
+#[default(error = ConfigError, impl_default)]
+enum Mode {
+    Off,
+
+    #[default]
+    Limited(#[default(PORT.parse()?)] u32, #[default(BAD_PORT.parse()?)] u32),
+}
+
+#[test]
+fn test_impl_default_panics_with_field() {
+    let result = std::panic::catch_unwind(Mode::default);
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+
+    assert!(message.starts_with("failed to compute the default value of `Mode::Limited::1`"));
+}
+
+#[derive(TryDefault, PartialEq, Debug)]
+#[default(error = ConfigError, impl_default)]
+struct Valid {
+    #[default(PORT.parse()?)]
+    port: u16,
+}
+
+#[test]
+fn test_impl_default() {
+    assert_eq!(Valid::default(), Valid { port: 8080 });
+}
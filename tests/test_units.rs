@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(idle: duration = "1h 30m", limit: bytes = "64MiB")]
+struct Limits {
+    #[default(duration = "30s")]
+    timeout: Duration,
+
+    #[default(duration = "1.5ms")]
+    tick: Duration,
+
+    #[default(duration = "2d")]
+    retention: Duration,
+
+    idle: Duration,
+
+    #[default(some duration = "0.25s")]
+    grace: Option<Duration>,
+
+    limit: u64,
+
+    #[default(bytes = "1.5KiB")]
+    chunk: u32,
+
+    #[default(bytes = "10KB")]
+    small: usize,
+
+    #[default(bytes = "2GB")]
+    large: i64,
+
+    #[default(bytes = "255B")]
+    byte: u8,
+}
+
+#[test]
+fn test_units() {
+    let limits = Limits::default();
+
+    assert_eq!(limits.timeout, Duration::from_secs(30));
+    assert_eq!(limits.tick, Duration::from_micros(1500));
+    assert_eq!(limits.retention, Duration::from_secs(2 * 24 * 3600));
+    assert_eq!(limits.idle, Duration::from_secs(90 * 60));
+    assert_eq!(limits.grace, Some(Duration::from_millis(250)));
+    assert_eq!(limits.limit, 64 * 1024 * 1024);
+    assert_eq!(limits.chunk, 1536);
+    assert_eq!(limits.small, 10_000);
+    assert_eq!(limits.large, 2_000_000_000);
+    assert_eq!(limits.byte, 255);
+}
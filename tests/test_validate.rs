@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+fn default_workers() -> u32 {
+    0
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(validate = Self::check)]
+struct Config {
+    #[default(8080, range = 1..=65535)]
+    port: u32,
+
+    #[default(-1.5, range = -10.0..0.0)]
+    offset: f64,
+
+    #[default(2, range = 1..)]
+    min_workers: u32,
+
+    #[default(profile = "large", 16)]
+    #[default(4, range = ..=64)]
+    max_workers: u32,
+}
+
+impl Config {
+    fn check(&self) -> Result<(), String> {
+        if self.min_workers > self.max_workers {
+            return Err(format!(
+                "`min_workers` ({}) is greater than `max_workers` ({})",
+                self.min_workers, self.max_workers
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_validate() {
+    let config = Config::default();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.offset, -1.5);
+    assert_eq!(config.min_workers, 2);
+    assert_eq!(config.max_workers, 4);
+    assert_eq!(Config::default_large().max_workers, 16);
+}
+
+#[derive(Default, Debug)]
+#[default(validate = |value: &Self| if value.0 < value.1 { Ok(()) } else { Err("empty range") })]
+struct Bounds(#[default(10)] i32, #[default(5)] i32);
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "invalid default value of `Bounds`: empty range")]
+fn test_validate_panics() {
+    Bounds::default();
+}
+
+#[derive(Default, Debug)]
+struct Workers {
+    #[default(default_workers(), range = 1..)]
+    count: u32,
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the default value of the field `count` is out of the range `1..`")]
+fn test_range_panics() {
+    Workers::default();
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(validate = Mode::check)]
+enum Mode {
+    #[default(retries: 3)]
+    Retry { retries: u8 },
+    Never,
+}
+
+impl Mode {
+    fn check(&self) -> Result<(), &'static str> {
+        match self {
+            Self::Retry { retries: 0 } => Err("no retries"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_validate_enum() {
+    assert_eq!(Mode::default(), Mode::Retry { retries: 3 });
+    assert_ne!(Mode::default(), Mode::Never);
+}
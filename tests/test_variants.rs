@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(variants)]
+enum Shape {
+    #[default]
+    Circle {
+        #[default(1.0)]
+        radius: f32,
+    },
+
+    Rectangle {
+        #[default(2.0)]
+        width: f32,
+
+        #[default(3.0)]
+        height: f32,
+    },
+
+    Polygon(#[default(6)] u32, Vec<f32>),
+
+    Point,
+}
+
+#[test]
+fn test_variant_constructors() {
+    assert_eq!(Shape::default_circle(), Shape::Circle { radius: 1.0 });
+    assert_eq!(
+        Shape::default_rectangle(),
+        Shape::Rectangle {
+            width: 2.0,
+            height: 3.0
+        }
+    );
+    assert_eq!(Shape::default_polygon(), Shape::Polygon(6, Vec::new()));
+    assert_eq!(Shape::default_point(), Shape::Point);
+}
+
+#[test]
+fn test_default_variant_is_unchanged() {
+    assert_eq!(Shape::default(), Shape::default_circle());
+}
+
+#[test]
+fn test_variant_defaults() {
+    assert_eq!(
+        Shape::variant_defaults(),
+        [
+            Shape::Circle { radius: 1.0 },
+            Shape::Rectangle {
+                width: 2.0,
+                height: 3.0
+            },
+            Shape::Polygon(6, Vec::new()),
+            Shape::Point,
+        ]
+    );
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(variants)]
+enum Protocol {
+    #[default]
+    HTTPServer,
+    WebSocket2,
+    Tcp,
+}
+
+#[test]
+fn test_snake_case_names() {
+    assert_eq!(Protocol::default_http_server(), Protocol::HTTPServer);
+    assert_eq!(Protocol::default_web_socket2(), Protocol::WebSocket2);
+    assert_eq!(Protocol::default_tcp(), Protocol::Tcp);
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(variants)]
+enum Maybe<T> {
+    #[default]
+    Nothing,
+    Just(T),
+}
+
+#[derive(PartialEq, Debug)]
+struct NoDefault;
+
+#[test]
+fn test_generic_variants() {
+    assert_eq!(Maybe::<u32>::default_just(), Maybe::Just(0));
+    assert_eq!(
+        Maybe::<u32>::variant_defaults(),
+        [Maybe::Nothing, Maybe::Just(0)]
+    );
+
+    // the variants with a generic field only need `Default` in their own constructor
+    assert!(matches!(
+        Maybe::<NoDefault>::default_nothing(),
+        Maybe::Nothing
+    ));
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(into_all)]
+enum Label {
+    #[default]
+    Empty,
+    Text(#[default("text")] String),
+}
+
+#[test]
+fn test_enum_options() {
+    assert_eq!(Label::default_text(), Label::Text("text".to_string()));
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Source {
+    #[default]
+    Stdin,
+    File(#[default("input.txt")] &'static str),
+    Handle(NoDefault),
+}
+
+#[test]
+fn test_constructors_of_overridden_variants() {
+    // without the `variants` option, only the variants with default values get a constructor
+    assert_eq!(Source::default_file(), Source::File("input.txt"));
+    assert_eq!(Source::default(), Source::Stdin);
+}
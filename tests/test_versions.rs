@@ -0,0 +1,78 @@
+use better_default::Default;
+
+#[derive(Default, PartialEq, Debug)]
+#[default(retries: 1)]
+struct Config {
+    #[default(50)]
+    #[default(since = 3, 100)]
+    #[default(since = 5, 200)]
+    timeout: u32,
+
+    #[default(since = 2, "zstd".to_string())]
+    compression: String,
+
+    #[default(since = 4, 3)]
+    retries: u8,
+
+    #[default(true)]
+    verbose: bool,
+}
+
+#[test]
+fn test_defaults_version() {
+    assert_eq!(Config::DEFAULTS_VERSION, 5);
+    assert_eq!(Config::default(), Config::default_for_version(Config::DEFAULTS_VERSION));
+}
+
+#[test]
+fn test_default_for_version() {
+    let config = |timeout, compression: &str, retries| Config {
+        timeout,
+        compression: compression.to_string(),
+        retries,
+        verbose: true,
+    };
+
+    assert_eq!(Config::default_for_version(0), config(50, "", 1));
+    assert_eq!(Config::default_for_version(2), config(50, "zstd", 1));
+    assert_eq!(Config::default_for_version(3), config(100, "zstd", 1));
+    assert_eq!(Config::default_for_version(4), config(100, "zstd", 3));
+    assert_eq!(Config::default_for_version(5), config(200, "zstd", 3));
+    assert_eq!(Config::default_for_version(u32::MAX), config(200, "zstd", 3));
+}
+
+#[derive(Default, PartialEq, Debug)]
+#[default(profile = "prod"; workers: 64)]
+struct Server {
+    #[default(4)]
+    #[default(since = 2, 8)]
+    workers: usize,
+
+    #[default(since = 2, 30)]
+    timeout: u64,
+}
+
+#[test]
+fn test_versions_with_profiles() {
+    assert_eq!(Server::default(), Server { workers: 8, timeout: 30 });
+    assert_eq!(Server::default_prod(), Server { workers: 64, timeout: 30 });
+    assert_eq!(Server::default_for_version(1), Server { workers: 4, timeout: 0 });
+}
+
+#[derive(Default, PartialEq, Debug)]
+enum Storage {
+    #[default]
+    Disk {
+        #[default(since = 2, 4096)]
+        block_size: u32,
+    },
+    Memory,
+}
+
+#[test]
+fn test_versions_on_enum() {
+    assert_eq!(Storage::DEFAULTS_VERSION, 2);
+    assert_eq!(Storage::default(), Storage::Disk { block_size: 4096 });
+    assert_eq!(Storage::default_for_version(1), Storage::Disk { block_size: 0 });
+    assert_ne!(Storage::default(), Storage::Memory);
+}
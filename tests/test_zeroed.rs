@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+
+use std::{
+    ffi::{c_char, c_int},
+    marker::PhantomData,
+    num::NonZeroU32,
+    ptr::NonNull,
+};
+
+use better_default::Default;
+
+#[derive(Default, Debug, PartialEq)]
+#[default(zeroed)]
+#[repr(C)]
+struct Header {
+    magic: u32,
+    flags: [u8; 100],
+    name: *const c_char,
+}
+
+#[derive(Default, Debug)]
+#[default(zeroed, version: 3)]
+#[repr(C)]
+struct Packet<T> {
+    header: Header,
+    version: c_int,
+    #[default(1.5)]
+    scale: f64,
+    next: Option<NonNull<Packet<T>>>,
+    id: Option<NonZeroU32>,
+    callback: Option<fn()>,
+    parent: Option<&'static Header>,
+    pair: (bool, char),
+    marker: PhantomData<T>,
+}
+
+#[test]
+fn test_zeroed() {
+    let header = Header::default();
+
+    assert_eq!(header.magic, 0);
+    assert_eq!(header.flags, [0; 100]);
+    assert!(header.name.is_null());
+}
+
+#[test]
+fn test_zeroed_overrides() {
+    let packet = Packet::<u8>::default();
+
+    assert_eq!(packet.header, Header::default());
+    assert_eq!(packet.version, 3);
+    assert_eq!(packet.scale, 1.5);
+    assert_eq!(packet.next, None);
+    assert_eq!(packet.id, None);
+    assert!(packet.callback.is_none());
+    assert_eq!(packet.parent, None);
+    assert_eq!(packet.pair, (false, '\0'));
+}
+
+#[derive(Default, Debug, PartialEq)]
+#[default(zeroed)]
+struct Tuple(u8, #[default(7)] i64);
+
+#[test]
+fn test_zeroed_tuple() {
+    assert_eq!(Tuple::default(), Tuple(0, 7));
+}
+
+trait Wrap {
+    type Out;
+}
+
+impl Wrap for u8 {
+    type Out = Header;
+}
+
+#[derive(Default)]
+#[default(zeroed)]
+#[repr(C)]
+struct Qualified {
+    // the qualified paths are checked by the marker of the type they resolve to
+    inner: <u8 as Wrap>::Out,
+}
+
+#[test]
+fn test_zeroed_qualified_path() {
+    assert_eq!(Qualified::default().inner, Header::default());
+}